@@ -9,6 +9,273 @@ use tauri::menu::{Menu, MenuItem};
 use tauri::tray::TrayIconBuilder;
 use tauri::{Emitter, Manager, State};
 
+// ============================================================================
+// Structured Errors
+// ============================================================================
+
+/// Structured alternative to the ad-hoc `Result<_, String>` used throughout
+/// the commands. Carries enough context (paths, argv, stderr tails) for the
+/// frontend to branch on `kind` instead of pattern-matching error strings.
+#[derive(Debug, thiserror::Error)]
+pub enum TranslationError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to parse config at {path}: {reason}")]
+    ConfigParse { path: String, reason: String },
+
+    #[error("failed to spawn dispatcher {argv:?}: {source}")]
+    DispatcherSpawn { argv: Vec<String>, source: String },
+
+    #[error("dispatcher exited with code {code}: {stderr_tail}")]
+    DispatcherExit { code: i32, stderr_tail: String },
+
+    #[error("missing required environment variable: {0}")]
+    MissingEnv(String),
+
+    #[error("project root not found")]
+    ProjectRootNotFound,
+
+    #[error("database schema version {db_version} is newer than this binary understands (max known version {max_version}); refusing to start")]
+    SchemaTooNew { db_version: i64, max_version: i64 },
+}
+
+impl Serialize for TranslationError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let message = self.to_string();
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            TranslationError::Io(_) => {
+                map.serialize_entry("kind", "io")?;
+                map.serialize_entry("message", &message)?;
+            }
+            TranslationError::ConfigParse { path, reason } => {
+                map.serialize_entry("kind", "config_parse")?;
+                map.serialize_entry("message", &message)?;
+                map.serialize_entry("path", path)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            TranslationError::DispatcherSpawn { argv, source } => {
+                map.serialize_entry("kind", "dispatcher_spawn")?;
+                map.serialize_entry("message", &message)?;
+                map.serialize_entry("argv", argv)?;
+                map.serialize_entry("source", source)?;
+            }
+            TranslationError::DispatcherExit { code, stderr_tail } => {
+                map.serialize_entry("kind", "dispatcher_exit")?;
+                map.serialize_entry("message", &message)?;
+                map.serialize_entry("code", code)?;
+                map.serialize_entry("stderr_tail", stderr_tail)?;
+            }
+            TranslationError::MissingEnv(key) => {
+                map.serialize_entry("kind", "missing_env")?;
+                map.serialize_entry("message", &message)?;
+                map.serialize_entry("key", key)?;
+            }
+            TranslationError::ProjectRootNotFound => {
+                map.serialize_entry("kind", "project_root_not_found")?;
+                map.serialize_entry("message", &message)?;
+            }
+            TranslationError::SchemaTooNew {
+                db_version,
+                max_version,
+            } => {
+                map.serialize_entry("kind", "schema_too_new")?;
+                map.serialize_entry("message", &message)?;
+                map.serialize_entry("db_version", db_version)?;
+                map.serialize_entry("max_version", max_version)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TranslationError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let kind = value.get("kind").and_then(|v| v.as_str()).unwrap_or("");
+        let message = value
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        Ok(match kind {
+            "config_parse" => TranslationError::ConfigParse {
+                path: value
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                reason: value
+                    .get("reason")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&message)
+                    .to_string(),
+            },
+            "dispatcher_spawn" => TranslationError::DispatcherSpawn {
+                argv: value
+                    .get("argv")
+                    .and_then(|v| v.as_array())
+                    .map(|a| {
+                        a.iter()
+                            .filter_map(|x| x.as_str().map(|s| s.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                source: value
+                    .get("source")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&message)
+                    .to_string(),
+            },
+            "dispatcher_exit" => TranslationError::DispatcherExit {
+                code: value.get("code").and_then(|v| v.as_i64()).unwrap_or(-1) as i32,
+                stderr_tail: value
+                    .get("stderr_tail")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&message)
+                    .to_string(),
+            },
+            "missing_env" => TranslationError::MissingEnv(
+                value
+                    .get("key")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&message)
+                    .to_string(),
+            ),
+            "project_root_not_found" => TranslationError::ProjectRootNotFound,
+            "schema_too_new" => TranslationError::SchemaTooNew {
+                db_version: value.get("db_version").and_then(|v| v.as_i64()).unwrap_or(0),
+                max_version: value
+                    .get("max_version")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0),
+            },
+            _ => TranslationError::ConfigParse {
+                path: String::new(),
+                reason: message,
+            },
+        })
+    }
+}
+
+// Most commands still return `Result<_, String>`; this lets inner helpers
+// migrate to `TranslationError` without forcing every call site to change
+// at once (see the later typed-error-codes pass for the rest).
+impl From<TranslationError> for String {
+    fn from(err: TranslationError) -> Self {
+        err.to_string()
+    }
+}
+
+/// The typed-error-codes pass referenced above: a stable `code` the frontend
+/// can branch on directly (instead of pattern-matching prose), a
+/// human-readable `message` for display/audit, and an optional
+/// `remediation` reusing the same `AlertRunbookAction` the alert runbooks
+/// already use to point the operator at the right tab.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppError {
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<AlertRunbookAction>,
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<AppError> for String {
+    fn from(err: AppError) -> Self {
+        err.message
+    }
+}
+
+impl AppError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        AppError {
+            code,
+            message: message.into(),
+            remediation: None,
+        }
+    }
+
+    fn with_remediation(
+        code: &'static str,
+        message: impl Into<String>,
+        label: &str,
+        tab: &str,
+    ) -> Self {
+        AppError {
+            code,
+            message: message.into(),
+            remediation: Some(AlertRunbookAction {
+                label: label.to_string(),
+                tab: tab.to_string(),
+            }),
+        }
+    }
+
+    fn preflight_blockers(message: impl Into<String>) -> Self {
+        Self::with_remediation("preflight-blockers", message, "Open Preflight", "dashboard")
+    }
+
+    fn gateway_start_failed(message: impl Into<String>) -> Self {
+        Self::with_remediation(
+            "gateway-start-failed",
+            message,
+            "Open Service Control",
+            "services",
+        )
+    }
+
+    fn invalid_job(message: impl Into<String>) -> Self {
+        Self::with_remediation("invalid-job", message, "Open Jobs", "jobs")
+    }
+
+    fn config_write_failed(message: impl Into<String>) -> Self {
+        Self::new("config-write-failed", message)
+    }
+
+    fn verify_failed(message: impl Into<String>) -> Self {
+        Self::with_remediation("verify-failed", message, "Open Service Control", "services")
+    }
+
+    fn startup_timeout(message: impl Into<String>) -> Self {
+        Self::with_remediation("startup-timeout", message, "Open Service Control", "services")
+    }
+
+    fn worker_start_failed(message: impl Into<String>) -> Self {
+        Self::with_remediation(
+            "worker-start-failed",
+            message,
+            "Open Service Control",
+            "services",
+        )
+    }
+
+    fn telegram_start_failed(message: impl Into<String>) -> Self {
+        Self::with_remediation("telegram-start-failed", message, "Open Logs", "logs")
+    }
+}
+
+impl From<TranslationError> for AppError {
+    fn from(err: TranslationError) -> Self {
+        AppError::new("internal-error", err.to_string())
+    }
+}
+
 // ============================================================================
 // Data Types
 // ============================================================================
@@ -22,11 +289,23 @@ pub struct ServiceStatus {
     pub restarts: u32,
 }
 
+/// Result of a single preflight check. `Unknown` absorbs any legacy or
+/// forward-compatible value so old snapshots still deserialize.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PreflightStatus {
+    Pass,
+    Warning,
+    Blocker,
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreflightCheck {
     pub name: String,
     pub key: String,
-    pub status: String, // "pass" | "warning" | "blocker"
+    pub status: PreflightStatus,
     pub message: String,
 }
 
@@ -80,6 +359,8 @@ pub struct StartupStepResult {
     pub hint_action: Option<String>,
     pub started_at: String,
     pub ended_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -132,6 +413,49 @@ pub struct JobInfo {
     pub sender: String,
     pub created_at: String,
     pub updated_at: String,
+    /// Number of retry attempts scheduled so far via `requeue_job`.
+    pub error_count: i64,
+    /// When the most recent retry attempt was scheduled, if any.
+    pub last_try: Option<String>,
+    /// When this job becomes eligible for `get_retryable_jobs`, if a retry
+    /// is currently scheduled.
+    pub next_try: Option<String>,
+    /// Number of times `requeue_failed_jobs` has reset this job back to
+    /// `pending`. Distinct from `error_count`, which tracks the single-job
+    /// `requeue_job` mechanism.
+    pub attempt_count: i64,
+    /// When this job becomes eligible to be picked up again after a
+    /// `requeue_failed_jobs` backoff delay, if one is scheduled.
+    pub next_retry_at: Option<String>,
+}
+
+/// Lifecycle of an async-spawned dispatcher job. Replaces the stringly-typed
+/// `JobInfo.status` for jobs started through `spawn_dispatcher`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobRunState {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobHandle {
+    pub job_id: String,
+    pub task_type: String,
+    pub state: JobRunState,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobResult {
+    pub job_id: String,
+    pub state: JobRunState,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -213,6 +537,27 @@ pub struct KbSyncReport {
     pub indexed_at: String,
 }
 
+/// Result of `poll_kb_sync`: either a fresh report plus the mtime it was
+/// observed at, or `modified: false` once `timeout_ms` elapses unmodified.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KbSyncPollResult {
+    pub modified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<KbSyncReport>,
+}
+
+/// Result of `poll_quality_report`, mirroring `KbSyncPollResult`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityReportPollResult {
+    pub modified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<QualityReport>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KbSourceGroupStat {
     pub source_group: String,
@@ -256,6 +601,63 @@ pub struct GlossaryTerm {
     pub source_path: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<String>,
+    /// Dotted version set (`node_id -> counter`) the glossary manager stamps
+    /// onto this term so concurrent batch upserts can tell an update from a
+    /// conflicting sibling instead of just overwriting by timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub causal_context: Option<HashMap<String, u64>>,
+}
+
+/// One row of a bulk import. `causal_context` is the version set this
+/// client last observed for the term (empty/omitted for a brand-new term);
+/// the glossary manager only overwrites the stored term when this context
+/// dominates the stored one, otherwise the write lands as a sibling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryBatchUpsertItem {
+    pub company: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub source_text: String,
+    pub target_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub causal_context: Option<HashMap<String, u64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryBatchDeleteItem {
+    pub company: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub source_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub causal_context: Option<HashMap<String, u64>>,
+}
+
+/// Surfaced when an upsert's `causal_context` didn't dominate the stored
+/// term's: the write was kept as a sibling instead of overwriting, and the
+/// UI should ask the user to pick a winner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryConflict {
+    pub stored_context: HashMap<String, u64>,
+    pub incoming_context: HashMap<String, u64>,
+    pub siblings: Vec<GlossaryTerm>,
+}
+
+/// Per-row outcome of `upsert_glossary_batch`/`delete_glossary_batch`, so one
+/// bad or conflicting row doesn't abort the rest of the import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryBatchItemResult {
+    pub company: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub source_text: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<GlossaryTerm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<GlossaryConflict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -294,17 +696,74 @@ pub struct GlossaryLookupResult {
 // API Provider Types
 // ============================================================================
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiAuthType {
+    Oauth,
+    ApiKey,
+    #[serde(rename = "none")]
+    NoAuth,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiProviderStatus {
+    Configured,
+    Missing,
+    Expired,
+    /// The stored secret exists but failed to decrypt (tampered, wrong
+    /// master key, or truncated payload) — distinct from `Missing`.
+    Corrupt,
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiProvider {
     pub id: String,
     pub name: String,
-    pub auth_type: String, // "oauth" | "api_key" | "none"
-    pub status: String,    // "configured" | "missing" | "expired"
+    pub auth_type: ApiAuthType,
+    pub status: ApiProviderStatus,
     pub has_key: bool,
     pub email: Option<String>,
     pub expires_at: Option<i64>,
 }
 
+/// Metadata for a single named API-key profile under a provider, as
+/// surfaced by `list_api_key_profiles`. Never carries the key itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyProfile {
+    pub provider: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub expires_at: Option<i64>,
+    pub has_key: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiUsageSource {
+    RealApi,
+    EstimatedActivity,
+    Unsupported,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiUsageConfidence {
+    High,
+    Medium,
+    Low,
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiUsage {
     pub provider: String,
@@ -315,13 +774,17 @@ pub struct ApiUsage {
     pub reset_at: Option<i64>,
     pub fetched_at: i64,
     // Extended fields for dual-track (real vs estimated)
-    pub source: String,     // "real_api" | "estimated_activity" | "unsupported"
-    pub confidence: String, // "high" | "medium" | "low"
+    pub source: ApiUsageSource,
+    pub confidence: ApiUsageConfidence,
     pub reason: Option<String>,
     pub activity_calls_24h: Option<u64>,
     pub activity_errors_24h: Option<u64>,
     pub activity_success_rate: Option<f64>,
     pub activity_last_seen_at: Option<i64>, // epoch ms
+    /// Only populated when the structured `activity.ndjson` log is present.
+    pub activity_rate_limited_24h: Option<u64>,
+    pub activity_p50_latency_ms: Option<u64>,
+    pub activity_p95_latency_ms: Option<u64>,
 }
 
 // ============================================================================
@@ -338,6 +801,11 @@ pub struct OverviewMetrics {
     pub backlog_jobs: u64,
     pub success_rate: f64,
     pub avg_turnaround_minutes: f64,
+    /// Rolling worker utilization over `period_hours`: the fraction (as a
+    /// percentage) of worker-hours actually busy, relative to
+    /// `worker_concurrency` hours of capacity. Near 100 means sustained
+    /// saturation; near 0 means idle workers despite any backlog.
+    pub occupancy_rate: f64,
     pub services_running: u64,
     pub services_total: u64,
     pub open_alerts: u64,
@@ -352,13 +820,55 @@ pub struct TrendPoint {
     pub value: i64,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Critical,
+    Warning,
+    Info,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Open,
+    Acknowledged,
+    Ignored,
+    #[serde(other)]
+    Unknown,
+}
+
+impl AlertSeverity {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Critical => "critical",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Info => "info",
+            AlertSeverity::Unknown => "unknown",
+        }
+    }
+}
+
+impl AlertStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AlertStatus::Open => "open",
+            AlertStatus::Acknowledged => "acknowledged",
+            AlertStatus::Ignored => "ignored",
+            AlertStatus::Unknown => "unknown",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertItem {
     pub id: String,
     pub title: String,
     pub message: String,
-    pub severity: String, // "critical" | "warning" | "info"
-    pub status: String,   // "open" | "acknowledged" | "ignored"
+    pub severity: AlertSeverity,
+    pub status: AlertStatus,
     pub source: String,
     pub metric_value: Option<i64>,
     pub created_at: i64,
@@ -375,6 +885,15 @@ pub struct QueueSnapshot {
     pub total: u64,
 }
 
+/// One row of the job-status histogram: how many jobs currently sit in a
+/// given raw `status` value, and the oldest `created_at` among them (for
+/// age-based pressure, e.g. "the oldest pending job has been waiting since").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCountBucket {
+    pub count: u64,
+    pub oldest_created_at: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunSummary {
     pub date: String,
@@ -405,6 +924,12 @@ pub struct ModelAvailabilityReport {
     pub agents: HashMap<String, AgentAvailability>,
     pub vision: VisionAvailability,
     pub glm: GlmAvailability,
+    pub provider_auth: HashMap<String, ProviderAuthSummary>,
+    /// Set when this report is a cached snapshot served after a failed
+    /// refresh attempt, so the frontend can flag it instead of presenting it
+    /// as current.
+    #[serde(default)]
+    pub stale: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -419,13 +944,24 @@ pub struct AgentAvailability {
     pub blocked_reasons: Vec<String>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteModelState {
+    Ok,
+    Cooldown,
+    Unavailable,
+    Expired,
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteModelStatus {
     pub model: String,
     pub provider: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub available: Option<bool>,
-    pub state: String, // "ok" | "cooldown" | "unavailable" | "expired" | "unknown"
+    pub state: RouteModelState,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cooldown_until_ms: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -444,6 +980,10 @@ pub struct VisionAvailability {
     pub vision_backend: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vision_model: Option<String>,
+    /// Priority-ordered backend ids that currently have credentials (e.g.
+    /// `["openai", "gemini"]` in `auto` mode). Callers walk this chain at
+    /// call time and advance past a failing entry rather than aborting.
+    pub resolved_chain: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -484,6 +1024,14 @@ struct AlertRunbookRuleConfig {
 struct AlertPolicyConfig {
     #[serde(default = "default_warning_to_critical_minutes")]
     warning_to_critical_minutes: u32,
+    /// How long a job can sit in an in-progress state with no `updated_at`
+    /// movement before it's flagged as stuck rather than merely slow.
+    #[serde(default = "default_stuck_job_minutes")]
+    stuck_job_minutes: u32,
+    /// Number of jobs the worker is expected to process concurrently, used
+    /// as the denominator for the `occupancy` overview trend.
+    #[serde(default = "default_worker_concurrency")]
+    worker_concurrency: u32,
     #[serde(default)]
     runbooks: Vec<AlertRunbookRuleConfig>,
 }
@@ -493,11 +1041,50 @@ pub struct AppState {
     pub alert_state: Mutex<AlertStateSnapshot>,
     pub alert_state_path: String,
     pub alert_policy_path: String,
+    /// Declarative fallback-chain priority, consumed by
+    /// `load_fallback_policy_config`/`compute_fallbacks_with_policy`.
+    pub fallback_policy_path: String,
     pub config_path: String,
     pub scripts_path: String,
     pub pids_dir: String,
     pub logs_dir: String,
     pub db_path: String,
+    /// Pooled connections to `db_path`, configured once at startup (WAL
+    /// mode, bounded pool size) instead of every job/event/alert-state read
+    /// reopening the file and re-running pragmas.
+    pub db_pool: r2d2::Pool<r2d2_sqlite::SqliteConnectionManager>,
+    pub jobs: Mutex<HashMap<String, JobHandle>>,
+    pub job_results: Mutex<Vec<JobResult>>,
+    /// Keyed by `"{action}_{status}"` (e.g. `"start_success"`), incremented
+    /// alongside the `best_effort_audit_operation` calls in
+    /// `perform_service_action_inner` and exported as Prometheus counters.
+    pub service_action_counts: Mutex<HashMap<String, u64>>,
+    /// Last good `ModelAvailabilityReport`, refreshed by
+    /// `run_availability_cache_refresher` instead of on every caller.
+    availability_cache: Mutex<Option<AvailabilityCacheEntry>>,
+    /// Per-provider backoff/retry bookkeeping driven by
+    /// `run_recovery_scheduler`.
+    provider_backoff: Mutex<HashMap<String, ProviderBackoffState>>,
+    /// One-line summary left by `reconcile_orphaned_jobs` at this process's
+    /// startup (e.g. "recovered 2 orphaned job(s)"), surfaced by
+    /// `export_run_summary`. `None` until reconciliation has run once.
+    reconciliation_summary: Mutex<Option<String>>,
+    /// Stop flags for in-flight `stream_container_logs` follows, keyed by
+    /// container name, so `stop_container_log_stream` can end a follow loop
+    /// without tearing down the whole app.
+    docker_log_streams: Mutex<HashMap<String, std::sync::Arc<std::sync::atomic::AtomicBool>>>,
+    /// Per-provider async locks so concurrent `refresh_oauth_token` callers
+    /// serialize instead of racing to spend the same refresh token.
+    oauth_refresh_locks: Mutex<HashMap<String, std::sync::Arc<tokio::sync::Mutex<()>>>>,
+    /// Last observed `GatewayStatus` per model, so `select_active_model_cached`
+    /// doesn't re-probe every model in the fallback chain on every call.
+    active_model_status_cache: Mutex<HashMap<String, ActiveModelStatusEntry>>,
+    /// Model last selected by `select_active_model_cached`, so a flip to a
+    /// different entry can be logged as a transition.
+    active_model_current: Mutex<Option<String>>,
+    /// Per-model circuit breaker state, tripped by a run of unhealthy probes
+    /// in `select_active_model_cached`.
+    circuit_breakers: Mutex<HashMap<String, ModelCircuitState>>,
 }
 
 fn detect_project_root() -> String {
@@ -526,6 +1113,229 @@ fn detect_project_root() -> String {
     "/Users/Code/workflow/Inifity".to_string()
 }
 
+/// Max simultaneous pooled SQLite connections. The dashboard polls
+/// jobs/events/alert-state on a timer from several commands at once, so a
+/// small fixed pool avoids both serializing on a single connection and
+/// unbounded file handles.
+const DB_POOL_MAX_SIZE: u32 = 8;
+
+fn build_db_pool(
+    db_path: &str,
+) -> Result<r2d2::Pool<r2d2_sqlite::SqliteConnectionManager>, r2d2::Error> {
+    let manager = r2d2_sqlite::SqliteConnectionManager::file(db_path).with_init(|conn| {
+        conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA busy_timeout = 5000;")
+    });
+    r2d2::Pool::builder().max_size(DB_POOL_MAX_SIZE).build(manager)
+}
+
+/// Single-connection in-memory fallback used only when `build_db_pool`
+/// can't open `db_path` at all (e.g. `runtime_dir` couldn't be created).
+/// Capped at one connection because separate `:memory:` connections don't
+/// share state, unlike the file-backed pool; a degraded-but-usable app
+/// beats the `.expect()` panic this replaces.
+fn build_fallback_memory_db_pool() -> r2d2::Pool<r2d2_sqlite::SqliteConnectionManager> {
+    let manager = r2d2_sqlite::SqliteConnectionManager::memory().with_init(|conn| {
+        conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA busy_timeout = 5000;")
+    });
+    r2d2::Pool::builder()
+        .max_size(1)
+        .build(manager)
+        .expect("failed to build in-memory fallback sqlite pool")
+}
+
+// ============================================================================
+// Schema Migrator
+// ============================================================================
+
+/// Reported by `get_schema_status` so the UI can warn before an upgrade
+/// that would bump the on-disk schema version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaStatus {
+    pub current_version: i64,
+    pub target_version: i64,
+}
+
+type SchemaMigrationFn = fn(&rusqlite::Connection) -> rusqlite::Result<()>;
+
+/// Ordered, idempotent migrations applied to `jobs`/`events` on `AppState`
+/// init. Each entry's version is the version the DB is at *after* that
+/// migration runs; `ensure_job_retry_columns`/`ensure_job_priority_column`
+/// are reused as-is since they were already written as idempotent
+/// `ALTER TABLE ... ADD COLUMN` steps.
+const SCHEMA_MIGRATIONS: &[(i64, &str, SchemaMigrationFn)] = &[
+    (1, "add job retry columns (error_count/last_try/next_try)", ensure_job_retry_columns),
+    (2, "add job priority column", ensure_job_priority_column),
+    (3, "add job attempt columns (attempt_count/next_retry_at)", ensure_job_attempt_columns),
+    (4, "create kb_files table and add content_hash/language columns", ensure_kb_files_columns),
+];
+
+/// Highest schema version this binary knows how to run against. A DB whose
+/// `schema_version` is higher means it was migrated by a newer binary.
+const SCHEMA_TARGET_VERSION: i64 = SCHEMA_MIGRATIONS[SCHEMA_MIGRATIONS.len() - 1].0;
+
+fn ensure_schema_version_table(conn: &rusqlite::Connection) -> rusqlite::Result<i64> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL)",
+    )?;
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))?;
+    if count == 0 {
+        conn.execute("INSERT INTO schema_version (version) VALUES (0)", [])?;
+        return Ok(0);
+    }
+    conn.query_row("SELECT version FROM schema_version LIMIT 1", [], |row| {
+        row.get(0)
+    })
+}
+
+/// Applies every migration newer than the DB's current `schema_version`,
+/// each inside its own transaction so a failed step rolls back cleanly
+/// instead of leaving the schema half-migrated. Refuses outright (without
+/// touching anything) if the DB is already newer than `SCHEMA_TARGET_VERSION`.
+fn run_schema_migrations(
+    pool: &r2d2::Pool<r2d2_sqlite::SqliteConnectionManager>,
+) -> Result<SchemaStatus, TranslationError> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| TranslationError::ConfigParse {
+            path: "schema_version".to_string(),
+            reason: format!("Failed to get pooled connection: {}", e),
+        })?;
+    let mut current_version = ensure_schema_version_table(&conn).map_err(|e| TranslationError::ConfigParse {
+        path: "schema_version".to_string(),
+        reason: format!("Failed to read schema_version: {}", e),
+    })?;
+
+    if current_version > SCHEMA_TARGET_VERSION {
+        return Err(TranslationError::SchemaTooNew {
+            db_version: current_version,
+            max_version: SCHEMA_TARGET_VERSION,
+        });
+    }
+
+    for (version, _description, migration) in SCHEMA_MIGRATIONS {
+        if *version <= current_version {
+            continue;
+        }
+        let tx = conn.transaction().map_err(|e| TranslationError::ConfigParse {
+            path: "schema_version".to_string(),
+            reason: format!("Failed to open migration transaction: {}", e),
+        })?;
+        migration(&tx).map_err(|e| TranslationError::ConfigParse {
+            path: "schema_version".to_string(),
+            reason: format!("Migration {} failed: {}", version, e),
+        })?;
+        tx.execute("UPDATE schema_version SET version = ?1", rusqlite::params![version])
+            .map_err(|e| TranslationError::ConfigParse {
+                path: "schema_version".to_string(),
+                reason: format!("Failed to record schema_version {}: {}", version, e),
+            })?;
+        tx.commit().map_err(|e| TranslationError::ConfigParse {
+            path: "schema_version".to_string(),
+            reason: format!("Failed to commit migration {}: {}", version, e),
+        })?;
+        current_version = *version;
+    }
+
+    Ok(SchemaStatus {
+        current_version,
+        target_version: SCHEMA_TARGET_VERSION,
+    })
+}
+
+#[tauri::command]
+fn get_schema_status(state: State<'_, AppState>) -> Result<SchemaStatus, String> {
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
+    let current_version = ensure_schema_version_table(&conn)
+        .map_err(|e| format!("Failed to read schema_version: {}", e))?;
+    Ok(SchemaStatus {
+        current_version,
+        target_version: SCHEMA_TARGET_VERSION,
+    })
+}
+
+/// Demotes jobs left in `running`/`round_N_done` by a worker that died
+/// mid-translation. Only touches rows whose `updated_at` predates this call
+/// (i.e. the job wasn't actively updated just now) and only when the Run
+/// Worker service isn't currently running, so a job a live worker is still
+/// processing is never reconciled out from under it. Jobs that only carried
+/// a bare `running` status go back to `pending`; jobs that already recorded
+/// partial round results go to `needs_attention` so a reviewer notices the
+/// gap instead of the job silently restarting from round 1.
+fn reconcile_orphaned_jobs_inner(state: &AppState) -> Result<u64, String> {
+    let services = get_service_status_inner(state).map_err(|e| e.to_string())?;
+    let worker_running = services
+        .iter()
+        .any(|s| s.name == "Run Worker" && s.status == "running");
+    if worker_running {
+        return Ok(0);
+    }
+
+    let started_at = now_iso();
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT job_id, status FROM jobs
+             WHERE status IN ('running', 'round_1_done', 'round_2_done', 'round_3_done')
+             AND updated_at < ?1",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let orphaned: Vec<(String, String)> = stmt
+        .query_map(rusqlite::params![started_at], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to query jobs: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect jobs: {}", e))?;
+    drop(stmt);
+
+    let now = now_iso();
+    let mut recovered = 0u64;
+    for (job_id, previous_status) in &orphaned {
+        let new_status = if previous_status == "running" {
+            "pending"
+        } else {
+            "needs_attention"
+        };
+        conn.execute(
+            "UPDATE jobs SET status = ?1, updated_at = ?2 WHERE job_id = ?3",
+            rusqlite::params![new_status, now, job_id],
+        )
+        .map_err(|e| format!("Failed to update job: {}", e))?;
+        insert_job_milestone(
+            &conn,
+            job_id,
+            "reconciled",
+            &now,
+            Some(
+                serde_json::json!({ "previous_status": previous_status, "new_status": new_status })
+                    .to_string(),
+            ),
+        )
+        .map_err(|e| format!("Failed to record milestone: {}", e))?;
+        recovered += 1;
+    }
+
+    if let Ok(mut summary) = state.reconciliation_summary.lock() {
+        *summary = Some(if recovered > 0 {
+            format!("recovered {} orphaned job(s)", recovered)
+        } else {
+            "no orphaned jobs found".to_string()
+        });
+    }
+
+    Ok(recovered)
+}
+
+#[tauri::command]
+fn reconcile_jobs(state: State<'_, AppState>) -> Result<u64, String> {
+    reconcile_orphaned_jobs_inner(&state)
+}
+
 impl Default for AppState {
     fn default() -> Self {
         let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/ivy".to_string());
@@ -534,17 +1344,45 @@ impl Default for AppState {
         let alert_state_path = format!("{}/alert_state.json", runtime_dir);
         let alert_state = load_alert_state_snapshot(&alert_state_path);
         let alert_policy_path = format!("{}/config/alert_policy.json", project_root);
+        let fallback_policy_path = format!("{}/config/fallback_policy.json", project_root);
+        let db_path = format!("{}/state.sqlite", runtime_dir);
+        if let Err(e) = fs::create_dir_all(&runtime_dir) {
+            eprintln!(
+                "[startup] failed to create runtime dir {}: {}",
+                runtime_dir, e
+            );
+        }
+        let db_pool = build_db_pool(&db_path).unwrap_or_else(|e| {
+            eprintln!(
+                "[startup] failed to open sqlite pool at {}: {}; falling back to an in-memory database",
+                db_path, e
+            );
+            build_fallback_memory_db_pool()
+        });
 
         Self {
             services: Mutex::new(HashMap::new()),
             alert_state: Mutex::new(alert_state),
             alert_state_path,
             alert_policy_path,
+            fallback_policy_path,
             config_path: project_root.clone(),
             scripts_path: format!("{}/scripts", project_root),
             pids_dir: format!("{}/pids", runtime_dir),
             logs_dir: format!("{}/logs", runtime_dir),
-            db_path: format!("{}/state.sqlite", runtime_dir),
+            db_path,
+            db_pool,
+            jobs: Mutex::new(HashMap::new()),
+            job_results: Mutex::new(Vec::new()),
+            service_action_counts: Mutex::new(HashMap::new()),
+            availability_cache: Mutex::new(None),
+            provider_backoff: Mutex::new(HashMap::new()),
+            reconciliation_summary: Mutex::new(None),
+            docker_log_streams: Mutex::new(HashMap::new()),
+            oauth_refresh_locks: Mutex::new(HashMap::new()),
+            active_model_status_cache: Mutex::new(HashMap::new()),
+            active_model_current: Mutex::new(None),
+            circuit_breakers: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -553,25 +1391,213 @@ impl Default for AppState {
 // Inner helper functions (avoid State<AppState> clone issues)
 // ============================================================================
 
-fn get_service_status_inner(state: &AppState) -> Result<Vec<ServiceStatus>, String> {
-    let mut services = vec![
-        ServiceStatus {
-            name: "Telegram Bot".to_string(),
-            status: "unknown".to_string(),
-            pid: None,
-            uptime: None,
-            restarts: 0,
-        },
-        ServiceStatus {
-            name: "Run Worker".to_string(),
-            status: "unknown".to_string(),
-            pid: None,
-            uptime: None,
-            restarts: 0,
-        },
-    ];
+// ============================================================================
+// Service Backends (PID-file vs. Docker Engine)
+// ============================================================================
+
+const DOCKER_SOCKET_PATH: &str = "/var/run/docker.sock";
+
+#[derive(Debug, Clone)]
+enum ServiceBackend {
+    PidFile,
+    Docker { container: String },
+}
+
+fn service_env_key(service_name: &str) -> String {
+    service_name.to_uppercase().replace(' ', "_")
+}
+
+/// Reads `OPENCLAW_SERVICE_BACKEND_<NAME>` / `OPENCLAW_SERVICE_DOCKER_CONTAINER_<NAME>`
+/// from `.env.v4.local` so PID-file and Docker-backed services can coexist.
+fn service_backend_for(state: &AppState, service_name: &str) -> ServiceBackend {
+    let env_map = read_env_map(&PathBuf::from(&state.config_path).join(".env.v4.local"));
+    let key = service_env_key(service_name);
+    match env_map
+        .get(&format!("OPENCLAW_SERVICE_BACKEND_{}", key))
+        .map(|s| s.trim().to_lowercase())
+    {
+        Some(backend) if backend == "docker" => {
+            let container = env_map
+                .get(&format!("OPENCLAW_SERVICE_DOCKER_CONTAINER_{}", key))
+                .cloned()
+                .unwrap_or_else(|| key.to_lowercase());
+            ServiceBackend::Docker { container }
+        }
+        _ => ServiceBackend::PidFile,
+    }
+}
+
+fn docker_socket_request(method: &str, path: &str) -> Result<Vec<u8>, String> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(DOCKER_SOCKET_PATH)
+        .map_err(|e| format!("Failed to connect to Docker socket: {}", e))?;
+    let request = format!(
+        "{} {} HTTP/1.1\r\nHost: docker\r\nConnection: close\r\n\r\n",
+        method, path
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| format!("Failed to write to Docker socket: {}", e))?;
+
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .map_err(|e| format!("Failed to read from Docker socket: {}", e))?;
+    Ok(response)
+}
+
+fn docker_response_body(raw: &[u8]) -> &[u8] {
+    raw.windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map(|i| &raw[i + 4..])
+        .unwrap_or(raw)
+}
+
+/// Un-chunks a `Transfer-Encoding: chunked` HTTP body into its raw payload.
+fn dechunk_http_body(body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < body.len() {
+        let Some(crlf) = body[i..].windows(2).position(|w| w == b"\r\n") else {
+            break;
+        };
+        let size_line = String::from_utf8_lossy(&body[i..i + crlf]);
+        let Ok(size) = usize::from_str_radix(size_line.trim(), 16) else {
+            break;
+        };
+        if size == 0 {
+            break;
+        }
+        let chunk_start = i + crlf + 2;
+        let chunk_end = (chunk_start + size).min(body.len());
+        out.extend_from_slice(&body[chunk_start..chunk_end]);
+        i = chunk_end + 2; // skip the chunk's trailing CRLF
+    }
+    out
+}
+
+fn docker_engine_get_json(path: &str) -> Result<serde_json::Value, String> {
+    let raw = docker_socket_request("GET", path)?;
+    let body = docker_response_body(&raw);
+    let unchunked = dechunk_http_body(body);
+    let payload = if unchunked.is_empty() { body } else { &unchunked };
+    serde_json::from_slice(payload).map_err(|e| format!("Failed to parse Docker API response: {}", e))
+}
+
+fn format_uptime_since(start: DateTime<Utc>) -> String {
+    let elapsed = Utc::now().signed_duration_since(start).num_minutes().max(0);
+    let hours = elapsed / 60;
+    if hours > 0 {
+        format!("{}h {}m", hours, elapsed % 60)
+    } else {
+        format!("{}m", elapsed)
+    }
+}
+
+/// Maps `GET /containers/{name}/json` onto the existing `ServiceStatus` shape,
+/// replacing the PID-file heuristics for container-backed services.
+fn docker_container_status(container: &str) -> Result<ServiceStatus, String> {
+    let inspect = docker_engine_get_json(&format!("/containers/{}/json", container))?;
+    let state = inspect.get("State").cloned().unwrap_or_default();
+    let running = state.get("Running").and_then(|v| v.as_bool()).unwrap_or(false);
+    let pid = state
+        .get("Pid")
+        .and_then(|v| v.as_u64())
+        .map(|p| p as u32)
+        .filter(|p| *p != 0);
+    let uptime = state
+        .get("StartedAt")
+        .and_then(|v| v.as_str())
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| format_uptime_since(ts.with_timezone(&Utc)));
+    let restarts = inspect
+        .get("RestartCount")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32;
+    let health_status = state
+        .get("Health")
+        .and_then(|h| h.get("Status"))
+        .and_then(|v| v.as_str());
+
+    let status = match health_status {
+        Some(h) => h.to_lowercase(),
+        None if running => "running".to_string(),
+        None => "stopped".to_string(),
+    };
+
+    Ok(ServiceStatus {
+        name: container.to_string(),
+        status,
+        pid,
+        uptime,
+        restarts,
+    })
+}
+
+/// `GET /containers/{id}/logs?stdout=1&stderr=1&tail=N`, demultiplexed into
+/// plain lines analogous to `TelegramHealth.log_tail`.
+fn docker_container_log_tail(container: &str, tail: u32) -> Result<Vec<String>, String> {
+    let raw = docker_socket_request(
+        "GET",
+        &format!("/containers/{}/logs?stdout=1&stderr=1&tail={}", container, tail),
+    )?;
+    let body = docker_response_body(&raw);
+    let unchunked = dechunk_http_body(body);
+    let payload = if unchunked.is_empty() { body } else { &unchunked };
+
+    let mut lines = Vec::new();
+    let mut i = 0;
+    while i + 8 <= payload.len() {
+        let frame_len =
+            u32::from_be_bytes([payload[i + 4], payload[i + 5], payload[i + 6], payload[i + 7]])
+                as usize;
+        let start = i + 8;
+        let end = (start + frame_len).min(payload.len());
+        if start < end {
+            lines.push(String::from_utf8_lossy(&payload[start..end]).trim_end().to_string());
+        }
+        i = end;
+    }
+    Ok(lines)
+}
+
+fn get_service_status_inner(state: &AppState) -> Result<Vec<ServiceStatus>, TranslationError> {
+    let mut services = vec![
+        ServiceStatus {
+            name: "Telegram Bot".to_string(),
+            status: "unknown".to_string(),
+            pid: None,
+            uptime: None,
+            restarts: 0,
+        },
+        ServiceStatus {
+            name: "Run Worker".to_string(),
+            status: "unknown".to_string(),
+            pid: None,
+            uptime: None,
+            restarts: 0,
+        },
+    ];
 
     for service in &mut services {
+        if let ServiceBackend::Docker { container } = service_backend_for(state, &service.name) {
+            match docker_container_status(&container) {
+                Ok(docker_status) => {
+                    service.status = docker_status.status;
+                    service.pid = docker_status.pid;
+                    service.uptime = docker_status.uptime;
+                    service.restarts = docker_status.restarts;
+                }
+                Err(e) => {
+                    eprintln!("[docker-backend] failed to inspect {}: {}", container, e);
+                    service.status = "unknown".to_string();
+                }
+            }
+            continue;
+        }
+
         let pid_candidates: Vec<PathBuf> = match service.name.as_str() {
             "Telegram Bot" => vec![
                 PathBuf::from(&state.pids_dir).join("telegram.pid"),
@@ -711,11 +1737,362 @@ fn read_env_map(env_path: &PathBuf) -> HashMap<String, String> {
     out
 }
 
-fn get_config_inner(state: &AppState) -> Result<AppConfig, String> {
+/// Soft/hard timeout thresholds (ms) for one startup phase. A phase that
+/// crosses `soft_ms` is still reported as a (non-fatal) warning step; one
+/// that crosses `hard_ms` aborts startup with a timeout error.
+struct PhaseTimeoutConfig {
+    soft_ms: u64,
+    hard_ms: u64,
+}
+
+/// Reads `OPENCLAW_STARTUP_<PHASE>_SOFT_TIMEOUT_MS` / `_HARD_TIMEOUT_MS`
+/// from `.env.v4.local` (via the same env map every other startup setting
+/// is read from), falling back to the given defaults when unset or
+/// unparsable.
+fn phase_timeout_config(
+    env_map: &HashMap<String, String>,
+    phase: &str,
+    soft_default_ms: u64,
+    hard_default_ms: u64,
+) -> PhaseTimeoutConfig {
+    let soft_ms = env_map
+        .get(&format!("OPENCLAW_STARTUP_{}_SOFT_TIMEOUT_MS", phase))
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(soft_default_ms);
+    let hard_ms = env_map
+        .get(&format!("OPENCLAW_STARTUP_{}_HARD_TIMEOUT_MS", phase))
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(hard_default_ms)
+        .max(soft_ms);
+    PhaseTimeoutConfig { soft_ms, hard_ms }
+}
+
+// ============================================================================
+// Typed Translation Config (validated layer over `.env.v4.local`)
+// ============================================================================
+
+/// Shared truthy parser for the `OPENCLAW_*_ENABLED`-style flags that used to
+/// each write their own `v.trim() == "1"` check.
+fn parse_truthy(value: &str) -> bool {
+    value.trim() == "1"
+}
+
+/// Which backend answers vision QA requests. Unlike the ad-hoc
+/// `backend_norm` string matching this replaces, an unrecognized value is a
+/// validation error rather than silently falling through to `auto`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VisionBackend {
+    Auto,
+    Gemini,
+    Moonshot,
+    Openai,
+}
+
+impl Default for VisionBackend {
+    fn default() -> Self {
+        VisionBackend::Auto
+    }
+}
+
+impl VisionBackend {
+    fn parse(raw: &str) -> Result<VisionBackend, String> {
+        match raw.trim().to_lowercase().as_str() {
+            "" | "auto" => Ok(VisionBackend::Auto),
+            "gemini" | "google" => Ok(VisionBackend::Gemini),
+            "moonshot" | "kimi" => Ok(VisionBackend::Moonshot),
+            "openai" | "openai-codex" => Ok(VisionBackend::Openai),
+            other => Err(format!(
+                "unknown OPENCLAW_VISION_BACKEND {:?} (expected auto, gemini, moonshot, or openai)",
+                other
+            )),
+        }
+    }
+}
+
+/// Typed, validated view over `.env.v4.local`, replacing the scattered
+/// `env_map.get(...).map(...)` chains that used to re-parse booleans and
+/// model strings inline in preflight and the auto-fix routine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationConfig {
+    pub work_root: String,
+    pub kb_root: String,
+    pub strict_router: bool,
+    pub require_new: bool,
+    pub rag_backend: String,
+    pub web_gateway_enabled: bool,
+    pub glm_enabled: bool,
+    pub primary_model: Option<String>,
+    pub kimi_model: Option<String>,
+    pub kimi_alt_model: Option<String>,
+    pub fallback_model: Option<String>,
+    pub image_model: Option<String>,
+    pub vision_backend: VisionBackend,
+    pub vision_model_override: Option<String>,
+    pub has_google_api_key: bool,
+    pub has_gemini_api_key: bool,
+    pub has_moonshot_api_key: bool,
+    pub has_openai_api_key: bool,
+    pub has_glm_api_key: bool,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        let registry = provider_registry();
+        TranslationConfig {
+            work_root: String::new(),
+            kb_root: String::new(),
+            strict_router: false,
+            require_new: false,
+            rag_backend: "local".to_string(),
+            web_gateway_enabled: false,
+            glm_enabled: false,
+            primary_model: registry
+                .get("openai-codex")
+                .and_then(|p| p.default_models().first())
+                .map(|s| s.to_string()),
+            kimi_model: registry
+                .get("moonshot")
+                .and_then(|p| p.default_models().first())
+                .map(|s| s.to_string()),
+            kimi_alt_model: Some("kimi-coding/k2p5".to_string()),
+            fallback_model: Some("kimi-coding/k2p5".to_string()),
+            image_model: None,
+            vision_backend: VisionBackend::Openai,
+            vision_model_override: None,
+            has_google_api_key: false,
+            has_gemini_api_key: false,
+            has_moonshot_api_key: false,
+            has_openai_api_key: false,
+            has_glm_api_key: false,
+        }
+    }
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Loads and validates `.env.v4.local` into a `TranslationConfig`. Returns a
+/// structured `TranslationError::ConfigParse` for fatal problems (currently
+/// just an unrecognized `OPENCLAW_VISION_BACKEND`), plus a list of
+/// non-fatal warnings (e.g. a selected vision backend with no matching key).
+fn load_translation_config(
+    env_path: &PathBuf,
+) -> Result<(TranslationConfig, Vec<String>), TranslationError> {
+    let env_map = read_env_map(env_path);
+    let registry = provider_registry();
+    let mut warnings: Vec<String> = Vec::new();
+
+    let vision_backend_raw = env_map
+        .get("OPENCLAW_VISION_BACKEND")
+        .cloned()
+        .unwrap_or_default();
+    let vision_backend = VisionBackend::parse(&vision_backend_raw).map_err(|reason| {
+        TranslationError::ConfigParse {
+            path: env_path.to_string_lossy().to_string(),
+            reason,
+        }
+    })?;
+
+    let has_google_api_key = env_map
+        .get("GOOGLE_API_KEY")
+        .map(|v| !v.trim().is_empty())
+        .unwrap_or(false);
+    let has_gemini_api_key = env_map
+        .get("GEMINI_API_KEY")
+        .map(|v| !v.trim().is_empty())
+        .unwrap_or(false);
+    let has_moonshot_api_key = registry
+        .get("moonshot")
+        .map(|p| p.is_env_configured(&env_map))
+        .unwrap_or(false);
+    let has_openai_api_key = registry
+        .get("openai-codex")
+        .map(|p| p.is_env_configured(&env_map))
+        .unwrap_or(false);
+    let has_glm_api_key = registry
+        .get("zai")
+        .map(|p| p.is_env_configured(&env_map))
+        .unwrap_or(false);
+
+    let backend_has_key = match vision_backend {
+        VisionBackend::Auto => true,
+        VisionBackend::Gemini => has_google_api_key || has_gemini_api_key,
+        VisionBackend::Moonshot => has_moonshot_api_key,
+        VisionBackend::Openai => has_openai_api_key,
+    };
+    if !backend_has_key {
+        warnings.push(format!(
+            "OPENCLAW_VISION_BACKEND is set to {:?} but no matching API key was found",
+            vision_backend
+        ));
+    }
+
+    let vision_model_override = match vision_backend {
+        VisionBackend::Moonshot => non_empty(env_map.get("OPENCLAW_MOONSHOT_VISION_MODEL"))
+            .or_else(|| non_empty(env_map.get("OPENCLAW_KIMI_VISION_MODEL"))),
+        VisionBackend::Openai => non_empty(env_map.get("OPENCLAW_OPENAI_VISION_MODEL")),
+        VisionBackend::Gemini => non_empty(env_map.get("OPENCLAW_GEMINI_VISION_MODEL")),
+        VisionBackend::Auto => non_empty(env_map.get("OPENCLAW_GEMINI_VISION_MODEL"))
+            .or_else(|| non_empty(env_map.get("OPENCLAW_MOONSHOT_VISION_MODEL")))
+            .or_else(|| non_empty(env_map.get("OPENCLAW_KIMI_VISION_MODEL")))
+            .or_else(|| non_empty(env_map.get("OPENCLAW_OPENAI_VISION_MODEL"))),
+    };
+
+    let glm_enabled = env_map
+        .get("OPENCLAW_GLM_ENABLED")
+        .map(|v| parse_truthy(v))
+        .unwrap_or(false);
+    if glm_enabled && !has_glm_api_key {
+        let has_zai_profile = false; // profile-based auth is checked separately against models status.
+        if !has_zai_profile {
+            warnings.push(
+                "OPENCLAW_GLM_ENABLED is set but GLM_API_KEY is missing (an OAuth profile may still cover it)"
+                    .to_string(),
+            );
+        }
+    }
+
+    let config = TranslationConfig {
+        work_root: env_map.get("V4_WORK_ROOT").cloned().unwrap_or_default(),
+        kb_root: env_map.get("V4_KB_ROOT").cloned().unwrap_or_default(),
+        strict_router: env_map
+            .get("OPENCLAW_STRICT_ROUTER")
+            .map(|v| parse_truthy(v))
+            .unwrap_or(false),
+        require_new: env_map
+            .get("OPENCLAW_REQUIRE_NEW")
+            .map(|v| parse_truthy(v))
+            .unwrap_or(false),
+        rag_backend: non_empty(env_map.get("OPENCLAW_RAG_BACKEND")).unwrap_or_else(|| "local".to_string()),
+        web_gateway_enabled: env_map
+            .get("OPENCLAW_WEB_GATEWAY_ENABLED")
+            .map(|v| parse_truthy(v))
+            .unwrap_or(false),
+        glm_enabled,
+        primary_model: non_empty(env_map.get("OPENCLAW_PRIMARY_MODEL")),
+        kimi_model: non_empty(env_map.get("OPENCLAW_KIMI_MODEL")),
+        kimi_alt_model: non_empty(env_map.get("OPENCLAW_KIMI_ALT_MODEL")),
+        fallback_model: non_empty(env_map.get("OPENCLAW_FALLBACK_MODEL")),
+        image_model: non_empty(env_map.get("OPENCLAW_IMAGE_MODEL")),
+        vision_backend,
+        vision_model_override,
+        has_google_api_key,
+        has_gemini_api_key,
+        has_moonshot_api_key,
+        has_openai_api_key,
+        has_glm_api_key,
+    };
+
+    Ok((config, warnings))
+}
+
+/// Renders the `.env.v4.local` template written by `auto_fix_preflight`
+/// when the file is missing, generated from `TranslationConfig`'s defaults
+/// so the template and the parser above can't drift apart.
+fn render_translation_config_template() -> String {
+    let defaults = TranslationConfig::default();
+    format!(
+        "# Translation system configuration\n\
+         V4_WORK_ROOT=\n\
+         V4_KB_ROOT=\n\
+         OPENCLAW_STRICT_ROUTER={strict_router}\n\
+         OPENCLAW_REQUIRE_NEW={require_new}\n\
+         OPENCLAW_RAG_BACKEND={rag_backend}\n\
+         OPENCLAW_KIMI_MODEL={kimi_model}\n\
+         OPENCLAW_KIMI_ALT_MODEL={kimi_alt_model}\n\
+         OPENCLAW_PRIMARY_MODEL={primary_model}\n\
+         OPENCLAW_FALLBACK_MODEL={fallback_model}\n\
+         OPENCLAW_IMAGE_MODEL={primary_model}\n\
+         # Vision QA backend: auto | gemini | moonshot | openai\n\
+         OPENCLAW_VISION_BACKEND={vision_backend}\n\
+         # Optional model overrides:\n\
+         # OPENCLAW_GEMINI_VISION_MODEL=gemini-3-pro\n\
+         # OPENCLAW_MOONSHOT_VISION_MODEL={kimi_model}\n\
+         # OPENCLAW_OPENAI_VISION_MODEL={primary_model}\n",
+        strict_router = if defaults.strict_router { 1 } else { 0 },
+        require_new = if defaults.require_new { 1 } else { 0 },
+        vision_backend = vision_backend_id(defaults.vision_backend),
+        rag_backend = defaults.rag_backend,
+        kimi_model = defaults.kimi_model.unwrap_or_default(),
+        kimi_alt_model = defaults.kimi_alt_model.unwrap_or_default(),
+        primary_model = defaults.primary_model.unwrap_or_default(),
+        fallback_model = defaults.fallback_model.unwrap_or_default(),
+    )
+}
+
+// ============================================================================
+// Vision QA Backend Resolver (cascading failover)
+// ============================================================================
+
+/// Stable, lowercase identifier for a vision backend, used in reports and in
+/// the `resolve_next_vision_backend` command.
+fn vision_backend_id(backend: VisionBackend) -> &'static str {
+    match backend {
+        VisionBackend::Auto => "auto",
+        VisionBackend::Gemini => "gemini",
+        VisionBackend::Moonshot => "moonshot",
+        VisionBackend::Openai => "openai",
+    }
+}
+
+/// Builds the ordered list of vision backends that currently have
+/// credentials. In `auto` mode this is the priority-ordered subset of
+/// openai/gemini/moonshot that's actually configured; for a pinned backend
+/// it's that backend alone (or empty, if it has no matching key). Callers
+/// walk this chain at call time and advance to the next entry on failure
+/// instead of aborting Format QA.
+fn resolve_vision_backend_chain(
+    backend: VisionBackend,
+    has_google_api_key: bool,
+    has_gemini_api_key: bool,
+    has_moonshot_api_key: bool,
+    has_openai_api_key: bool,
+) -> Vec<VisionBackend> {
+    let configured = |candidate: VisionBackend| -> bool {
+        match candidate {
+            VisionBackend::Auto => false,
+            VisionBackend::Gemini => has_google_api_key || has_gemini_api_key,
+            VisionBackend::Moonshot => has_moonshot_api_key,
+            VisionBackend::Openai => has_openai_api_key,
+        }
+    };
+    match backend {
+        VisionBackend::Auto => [
+            VisionBackend::Openai,
+            VisionBackend::Gemini,
+            VisionBackend::Moonshot,
+        ]
+        .into_iter()
+        .filter(|candidate| configured(*candidate))
+        .collect(),
+        pinned if configured(pinned) => vec![pinned],
+        _ => Vec::new(),
+    }
+}
+
+/// Renders e.g. "auto → openai, gemini" for the "Vision QA Keys" preflight
+/// message, so an operator can see the resolved failover order rather than
+/// just the raw `OPENCLAW_VISION_BACKEND` setting.
+fn format_vision_backend_chain(backend: VisionBackend, chain: &[VisionBackend]) -> String {
+    if chain.is_empty() {
+        format!("{} → (none configured)", vision_backend_id(backend))
+    } else {
+        let names: Vec<&'static str> = chain.iter().map(|b| vision_backend_id(*b)).collect();
+        format!("{} → {}", vision_backend_id(backend), names.join(", "))
+    }
+}
+
+fn get_config_inner(state: &AppState) -> Result<AppConfig, TranslationError> {
     let env_path = format!("{}/.env.v4.local", state.config_path);
 
-    let content =
-        fs::read_to_string(&env_path).map_err(|e| format!("Failed to read config: {}", e))?;
+    let content = fs::read_to_string(&env_path).map_err(|e| TranslationError::ConfigParse {
+        path: env_path.clone(),
+        reason: e.to_string(),
+    })?;
 
     let mut config = AppConfig {
         work_root: String::new(),
@@ -778,7 +2155,7 @@ fn dispatcher_notify_target(state: &AppState) -> String {
         .unwrap_or_default()
 }
 
-fn run_dispatcher_json(state: &AppState, args: &[&str]) -> Result<serde_json::Value, String> {
+fn run_dispatcher_json(state: &AppState, args: &[&str]) -> Result<serde_json::Value, TranslationError> {
     let config = get_config_inner(state)?;
     let python_bin = find_python_bin(state);
     let notify_target = dispatcher_notify_target(state);
@@ -799,7 +2176,10 @@ fn run_dispatcher_json(state: &AppState, args: &[&str]) -> Result<serde_json::Va
         .args(&cmd_args)
         .current_dir(&state.config_path)
         .output()
-        .map_err(|e| format!("Failed to run dispatcher {:?}: {}", args, e))?;
+        .map_err(|e| TranslationError::DispatcherSpawn {
+            argv: cmd_args.clone(),
+            source: e.to_string(),
+        })?;
 
     if !output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
@@ -809,12 +2189,125 @@ fn run_dispatcher_json(state: &AppState, args: &[&str]) -> Result<serde_json::Va
         } else {
             stdout
         };
-        return Err(format!("dispatcher {:?} failed: {}", args, detail));
+        let code = output.status.code().unwrap_or(-1);
+        capture_dispatcher_crash(state, &cmd_args, code, &detail);
+        return Err(TranslationError::DispatcherExit {
+            code,
+            stderr_tail: detail,
+        });
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    serde_json::from_str::<serde_json::Value>(&stdout)
-        .map_err(|e| format!("Failed to parse dispatcher output: {}", e))
+    serde_json::from_str::<serde_json::Value>(&stdout).map_err(|e| TranslationError::ConfigParse {
+        path: "<dispatcher stdout>".to_string(),
+        reason: format!("Failed to parse dispatcher output: {}", e),
+    })
+}
+
+fn dispatcher_argv(state: &AppState, args: &[String]) -> Result<(String, Vec<String>), String> {
+    let config = get_config_inner(state)?;
+    let python_bin = find_python_bin(state);
+    let notify_target = dispatcher_notify_target(state);
+
+    let mut cmd_args: Vec<String> = vec![
+        "-m".to_string(),
+        "scripts.openclaw_v4_dispatcher".to_string(),
+        "--work-root".to_string(),
+        config.work_root,
+        "--kb-root".to_string(),
+        config.kb_root,
+        "--notify-target".to_string(),
+        notify_target,
+    ];
+    cmd_args.extend(args.iter().cloned());
+    Ok((python_bin, cmd_args))
+}
+
+/// Spawns a dispatcher run without blocking the caller: the child is handed
+/// to `tokio::process::Command` and awaited on a detached task that records
+/// the `JobResult` and emits `job-updated` once it exits. Mirrors the
+/// pop-completed pattern used by the agent-executor subsystem.
+#[tauri::command]
+async fn spawn_dispatcher(
+    args: Vec<String>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let job_id = format!("job-{}", now_epoch_ms());
+    let (python_bin, cmd_args) = dispatcher_argv(&state, &args)?;
+
+    let mut child = tokio::process::Command::new(&python_bin)
+        .args(&cmd_args)
+        .current_dir(&state.config_path)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn dispatcher {:?}: {}", cmd_args, e))?;
+
+    let now = now_iso();
+    state.jobs.lock().unwrap().insert(
+        job_id.clone(),
+        JobHandle {
+            job_id: job_id.clone(),
+            task_type: "dispatcher".to_string(),
+            state: JobRunState::Running,
+            created_at: now.clone(),
+            updated_at: now,
+        },
+    );
+    let _ = app.emit(
+        "job-updated",
+        serde_json::json!({ "job_id": job_id, "state": JobRunState::Running }),
+    );
+
+    let job_id_for_task = job_id.clone();
+    let app_for_task = app.clone();
+    tokio::spawn(async move {
+        let outcome = child.wait_with_output().await;
+        let (new_state, stdout, stderr, exit_code) = match outcome {
+            Ok(output) if output.status.success() => (
+                JobRunState::Done,
+                String::from_utf8_lossy(&output.stdout).to_string(),
+                String::from_utf8_lossy(&output.stderr).to_string(),
+                output.status.code(),
+            ),
+            Ok(output) => (
+                JobRunState::Failed,
+                String::from_utf8_lossy(&output.stdout).to_string(),
+                String::from_utf8_lossy(&output.stderr).to_string(),
+                output.status.code(),
+            ),
+            Err(e) => (JobRunState::Failed, String::new(), e.to_string(), None),
+        };
+
+        let app_state = app_for_task.state::<AppState>();
+        if let Some(handle) = app_state.jobs.lock().unwrap().get_mut(&job_id_for_task) {
+            handle.state = new_state.clone();
+            handle.updated_at = now_iso();
+        }
+        app_state.job_results.lock().unwrap().push(JobResult {
+            job_id: job_id_for_task.clone(),
+            state: new_state.clone(),
+            stdout,
+            stderr,
+            exit_code,
+        });
+        let _ = app_for_task.emit(
+            "job-updated",
+            serde_json::json!({ "job_id": job_id_for_task, "state": new_state }),
+        );
+    });
+
+    Ok(job_id)
+}
+
+/// Drains results for jobs that finished since the last poll. The UI calls
+/// this on an interval (or after a `job-updated` event) to stream progress
+/// without blocking on the dispatcher itself.
+#[tauri::command]
+fn poll_completed(state: State<'_, AppState>) -> Result<Vec<JobResult>, String> {
+    let mut results = state.job_results.lock().unwrap();
+    Ok(std::mem::take(&mut *results))
 }
 
 fn parse_gateway_status(value: &serde_json::Value) -> GatewayStatus {
@@ -1189,11 +2682,215 @@ fn audit_operation_inner(
         args.push(detail.to_string());
     }
     let refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    run_dispatcher_json(state, &refs)
+    run_dispatcher_json(state, &refs).map_err(|e| e.to_string())
 }
 
 fn best_effort_audit_operation(state: &AppState, payload: AuditOperationPayload) {
     let _ = audit_operation_inner(state, &payload);
+    export_otel_audit_event(state, &payload);
+}
+
+// ============================================================================
+// OpenTelemetry Export (best-effort, OTLP/HTTP+JSON)
+// ============================================================================
+
+/// `.env.v4.local` key (or process env fallback) that turns OTEL export on.
+/// Unset means a clean no-op: nothing here changes existing best-effort
+/// behavior.
+const OTEL_ENDPOINT_ENV_KEY: &str = "OPENCLAW_OTEL_EXPORTER_OTLP_ENDPOINT";
+
+fn otel_otlp_endpoint(state: &AppState) -> Option<String> {
+    let env_map = read_env_map(&PathBuf::from(&state.config_path).join(".env.v4.local"));
+    env_map
+        .get(OTEL_ENDPOINT_ENV_KEY)
+        .cloned()
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| {
+            std::env::var(OTEL_ENDPOINT_ENV_KEY)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+        })
+}
+
+/// One timestamped sub-event within a span -- e.g. one `PreflightCheck`, or
+/// an audit payload mirrored alongside its local record.
+#[derive(Debug, Clone)]
+struct OtelSpanEvent {
+    name: String,
+    time_unix_nano: u128,
+    attributes: serde_json::Value,
+}
+
+/// A single unit of work to export: a preflight run, a component
+/// start/stop/restart, or (for audit payloads) a standalone single-event
+/// span. `events` become OTLP span events; `attributes` become span-level
+/// attributes (e.g. pass/warning/blocker tallies).
+#[derive(Debug, Clone)]
+struct OtelSpan {
+    name: String,
+    start_unix_nano: u128,
+    end_unix_nano: u128,
+    status_ok: bool,
+    attributes: serde_json::Value,
+    events: Vec<OtelSpanEvent>,
+}
+
+fn otel_now_unix_nano() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+/// Cheap dependency-free id generator in the same spirit as the recovery
+/// scheduler's `jitter_ms`: not cryptographically random, good enough to
+/// tell spans apart in a trace viewer.
+fn otel_hex_id(byte_len: usize) -> String {
+    let nanos = otel_now_unix_nano();
+    let mut out = String::with_capacity(byte_len * 2);
+    for i in 0..byte_len {
+        let shifted = ((nanos >> ((i % 16) * 4)) & 0xF) as u8;
+        out.push_str(&format!("{:x}", shifted.wrapping_add(i as u8) & 0xF));
+    }
+    out
+}
+
+fn otel_attributes_to_kv(attributes: &serde_json::Value) -> Vec<serde_json::Value> {
+    let Some(obj) = attributes.as_object() else {
+        return Vec::new();
+    };
+    obj.iter()
+        .map(|(k, v)| {
+            let value = if let Some(s) = v.as_str() {
+                serde_json::json!({ "stringValue": s })
+            } else if let Some(b) = v.as_bool() {
+                serde_json::json!({ "boolValue": b })
+            } else if let Some(n) = v.as_i64() {
+                serde_json::json!({ "intValue": n.to_string() })
+            } else {
+                serde_json::json!({ "stringValue": v.to_string() })
+            };
+            serde_json::json!({ "key": k, "value": value })
+        })
+        .collect()
+}
+
+fn otel_span_to_otlp_json(span: &OtelSpan) -> serde_json::Value {
+    let trace_id = otel_hex_id(16);
+    let span_id = otel_hex_id(8);
+    serde_json::json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": "openclaw-translation-desktop" } }
+                ]
+            },
+            "scopeSpans": [{
+                "scope": { "name": "translation.lifecycle" },
+                "spans": [{
+                    "traceId": trace_id,
+                    "spanId": span_id,
+                    "name": span.name,
+                    "startTimeUnixNano": span.start_unix_nano.to_string(),
+                    "endTimeUnixNano": span.end_unix_nano.to_string(),
+                    "status": {
+                        "code": if span.status_ok { "STATUS_CODE_OK" } else { "STATUS_CODE_ERROR" }
+                    },
+                    "attributes": otel_attributes_to_kv(&span.attributes),
+                    "events": span.events.iter().map(|e| serde_json::json!({
+                        "name": e.name,
+                        "timeUnixNano": e.time_unix_nano.to_string(),
+                        "attributes": otel_attributes_to_kv(&e.attributes),
+                    })).collect::<Vec<_>>(),
+                }]
+            }]
+        }]
+    })
+}
+
+async fn post_otlp_json(
+    endpoint: &str,
+    path: &str,
+    body: &serde_json::Value,
+) -> Result<(), String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let without_scheme = endpoint
+        .trim_end_matches('/')
+        .trim_start_matches("http://")
+        .trim_start_matches("https://");
+    let (host, port) = match without_scheme.split_once(':') {
+        Some((host, port)) => (host.to_string(), port.parse::<u16>().unwrap_or(4318)),
+        None => (without_scheme.to_string(), 4318),
+    };
+
+    let payload = serde_json::to_vec(body).map_err(|e| e.to_string())?;
+    let mut stream = tokio::net::TcpStream::connect((host.as_str(), port))
+        .await
+        .map_err(|e| format!("Failed to connect to OTLP endpoint {}:{}: {}", host, port, e))?;
+
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        path,
+        host,
+        payload.len()
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write OTLP request: {}", e))?;
+    stream
+        .write_all(&payload)
+        .await
+        .map_err(|e| format!("Failed to write OTLP payload: {}", e))?;
+
+    let mut response = Vec::new();
+    let _ = stream.read_to_end(&mut response).await;
+    Ok(())
+}
+
+/// Fire-and-forget OTLP/HTTP+JSON export of `span` to `<endpoint>/v1/traces`.
+/// Spawned so callers never block on telemetry; failures are swallowed the
+/// same way `best_effort_audit_operation` swallows local audit-log errors.
+fn export_otel_span(state: &AppState, span: OtelSpan) {
+    let Some(endpoint) = otel_otlp_endpoint(state) else {
+        return;
+    };
+    tokio::spawn(async move {
+        let _ = post_otlp_json(&endpoint, "/v1/traces", &otel_span_to_otlp_json(&span)).await;
+    });
+}
+
+/// Mirrors one audit payload as a standalone single-event span, so traces
+/// and the local audit log stay in sync without every `best_effort_audit_operation`
+/// call site needing its own span plumbing.
+fn export_otel_audit_event(state: &AppState, payload: &AuditOperationPayload) {
+    if otel_otlp_endpoint(state).is_none() {
+        return;
+    }
+    let now = otel_now_unix_nano();
+    export_otel_span(
+        state,
+        OtelSpan {
+            name: format!("audit.{}", payload.action),
+            start_unix_nano: now,
+            end_unix_nano: now,
+            status_ok: payload.status != "failed",
+            attributes: serde_json::json!({
+                "source": payload.source,
+                "action": payload.action,
+                "status": payload.status,
+            }),
+            events: vec![OtelSpanEvent {
+                name: payload.action.clone(),
+                time_unix_nano: now,
+                attributes: serde_json::json!({
+                    "summary": payload.summary,
+                    "detail": payload.detail,
+                }),
+            }],
+        },
+    );
 }
 
 fn fmt_epoch_ms(ms: i64) -> String {
@@ -1273,62 +2970,87 @@ fn run_openclaw_json(args: &[&str]) -> Result<serde_json::Value, String> {
         .map_err(|e| format!("Failed to parse openclaw JSON output: {}", e))
 }
 
-fn compute_fallbacks_with_kimi_defaults(
-    current: Vec<String>,
-    kimi_model: &str,
-    kimi_alt_model: &str,
-    fallback_model: &str,
-) -> Vec<String> {
-    let kimi = kimi_model.trim();
-    let kimi_alt = kimi_alt_model.trim();
-    let fallback = fallback_model.trim();
-    let mut seen: HashSet<String> = HashSet::new();
-    let mut out: Vec<String> = Vec::new();
+/// Declarative format for `compute_fallbacks_with_policy`: an ordered list
+/// of models to hoist to the front of the fallback chain, plus prefixes
+/// whose matches are pushed to the very end. Lets new provider families
+/// (e.g. a new coding model) be prioritized via config instead of a crate
+/// change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackPolicyConfig {
+    /// Models hoisted to the front of the chain, in priority order.
+    #[serde(default)]
+    pub preferred: Vec<String>,
+    /// Prefixes (e.g. `"zai/glm-"`) whose matching entries are moved to the
+    /// very end of the chain, in the order the prefixes are listed.
+    #[serde(default)]
+    pub append_prefixes: Vec<String>,
+}
 
-    for item in current {
-        let m = item.trim();
+fn default_fallback_policy_config() -> FallbackPolicyConfig {
+    FallbackPolicyConfig {
+        preferred: vec!["moonshot/kimi-k2.5".to_string(), "kimi-coding/k2p5".to_string()],
+        append_prefixes: vec!["zai/glm-".to_string()],
+    }
+}
+
+/// Loads `state.fallback_policy_path`, falling back to
+/// `default_fallback_policy_config` when the file is absent or invalid.
+fn load_fallback_policy_config(state: &AppState) -> FallbackPolicyConfig {
+    let path = PathBuf::from(&state.fallback_policy_path);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return default_fallback_policy_config(),
+    };
+    serde_json::from_str(&content).unwrap_or_else(|_| default_fallback_policy_config())
+}
+
+/// Reorders `current` per `policy`: `policy.preferred` entries are hoisted
+/// to the front (deduped against each other and removed from their
+/// original position in `current`), `policy.append_prefixes` matches are
+/// moved to the very end in the order the prefixes are listed, everything
+/// else keeps its relative order, and the whole result is deduped
+/// preserving first occurrence.
+fn compute_fallbacks_with_policy(current: Vec<String>, policy: &FallbackPolicyConfig) -> Vec<String> {
+    let mut head: Vec<String> = Vec::new();
+    for model in &policy.preferred {
+        let m = model.trim();
         if m.is_empty() {
             continue;
         }
-        if (!kimi.is_empty() && m == kimi)
-            || (!kimi_alt.is_empty() && m == kimi_alt)
-            || (!fallback.is_empty() && m == fallback)
-        {
-            continue;
-        }
-        if seen.insert(m.to_string()) {
-            out.push(m.to_string());
+        if !head.iter().any(|h| h == m) {
+            head.push(m.to_string());
         }
     }
 
-    let mut head: Vec<String> = Vec::new();
-    for model in [kimi, kimi_alt, fallback] {
-        if model.is_empty() {
+    let mut out: Vec<String> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for item in current {
+        let m = item.trim();
+        if m.is_empty() || head.iter().any(|h| h == m) {
             continue;
         }
-        if !head.iter().any(|m| m == model) {
-            head.push(model.to_string());
+        if seen.insert(m.to_string()) {
+            out.push(m.to_string());
         }
     }
 
-    if head.is_empty() {
-        return out;
-    }
-
-    let mut non_glm: Vec<String> = Vec::new();
-    let mut glm: Vec<String> = Vec::new();
+    let mut buckets: Vec<Vec<String>> =
+        (0..=policy.append_prefixes.len()).map(|_| Vec::new()).collect();
     for model in out {
-        if model.starts_with("zai/glm-") {
-            glm.push(model);
-        } else {
-            non_glm.push(model);
-        }
+        let bucket = policy
+            .append_prefixes
+            .iter()
+            .position(|prefix| model.starts_with(prefix.as_str()))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        buckets[bucket].push(model);
     }
 
     let mut desired: Vec<String> = Vec::new();
     desired.extend(head);
-    desired.extend(non_glm);
-    desired.extend(glm);
+    for bucket in buckets {
+        desired.extend(bucket);
+    }
 
     let mut deduped: Vec<String> = Vec::new();
     let mut dedup_seen: HashSet<String> = HashSet::new();
@@ -1340,6 +3062,226 @@ fn compute_fallbacks_with_kimi_defaults(
     deduped
 }
 
+/// `compute_fallbacks_with_policy`, built from the legacy Kimi
+/// primary/secondary + GLM-last special case rather than a loaded
+/// `FallbackPolicyConfig`. Kept for the env-var-driven call sites;
+/// `load_fallback_policy_config` is the declarative replacement.
+fn compute_fallbacks_with_kimi_defaults(
+    current: Vec<String>,
+    kimi_model: &str,
+    kimi_alt_model: &str,
+    fallback_model: &str,
+) -> Vec<String> {
+    let policy = FallbackPolicyConfig {
+        preferred: vec![
+            kimi_model.to_string(),
+            kimi_alt_model.to_string(),
+            fallback_model.to_string(),
+        ],
+        append_prefixes: vec!["zai/glm-".to_string()],
+    };
+    compute_fallbacks_with_policy(current, &policy)
+}
+
+/// How long a per-model `GatewayStatus` probe is trusted before
+/// `select_active_model_cached` re-queries it via `status_of`.
+const ACTIVE_MODEL_STATUS_TTL_MS: i64 = 10_000;
+
+#[derive(Debug, Clone)]
+struct ActiveModelStatusEntry {
+    status: GatewayStatus,
+    cached_at_ms: i64,
+}
+
+/// How many recent health-probe outcomes a model's circuit breaker
+/// considers.
+const CIRCUIT_WINDOW_SIZE: usize = 10;
+/// Don't trip the breaker on fewer samples than this -- a single flaky
+/// probe shouldn't take a model out of rotation.
+const CIRCUIT_MIN_SAMPLES: usize = 3;
+/// Trip `Closed` to `Open` once at least this share of the window's
+/// outcomes failed.
+const CIRCUIT_FAILURE_RATIO: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitState {
+    /// Normal operation: the model is probed and selectable like any other.
+    Closed,
+    /// Tripped: the model is skipped until its backoff elapses.
+    Open,
+    /// Backoff elapsed; the next probe is a single trial request.
+    HalfOpen,
+}
+
+#[derive(Debug, Clone)]
+struct ModelCircuitState {
+    state: CircuitState,
+    outcomes: std::collections::VecDeque<bool>,
+    consecutive_failures: u32,
+    opened_at_ms: i64,
+}
+
+impl Default for ModelCircuitState {
+    fn default() -> Self {
+        ModelCircuitState {
+            state: CircuitState::Closed,
+            outcomes: std::collections::VecDeque::new(),
+            consecutive_failures: 0,
+            opened_at_ms: 0,
+        }
+    }
+}
+
+/// Returns whether `model` may currently be probed, transitioning an `Open`
+/// breaker to `HalfOpen` once `next_backoff_delay_ms` worth of cooldown has
+/// elapsed since it tripped.
+fn circuit_allows_probe(state: &AppState, model: &str, now_ms: i64) -> bool {
+    let mut breakers = state.circuit_breakers.lock().unwrap();
+    let breaker = breakers.entry(model.to_string()).or_default();
+    match breaker.state {
+        CircuitState::Closed | CircuitState::HalfOpen => true,
+        CircuitState::Open => {
+            let backoff = next_backoff_delay_ms(breaker.consecutive_failures);
+            if now_ms - breaker.opened_at_ms >= backoff {
+                breaker.state = CircuitState::HalfOpen;
+                true
+            } else {
+                false
+            }
+        }
+    }
+}
+
+/// Records one health-probe outcome for `model`. In `HalfOpen`, a success
+/// closes the breaker and resets its backoff, while a failure re-opens it
+/// at the next backoff step. In `Closed`, a run of failures within the
+/// sliding window trips the breaker to `Open`.
+fn circuit_record_outcome(state: &AppState, model: &str, healthy: bool, now_ms: i64) {
+    let mut breakers = state.circuit_breakers.lock().unwrap();
+    let breaker = breakers.entry(model.to_string()).or_default();
+
+    match breaker.state {
+        CircuitState::HalfOpen => {
+            if healthy {
+                breaker.state = CircuitState::Closed;
+                breaker.consecutive_failures = 0;
+                breaker.outcomes.clear();
+            } else {
+                breaker.consecutive_failures += 1;
+                breaker.state = CircuitState::Open;
+                breaker.opened_at_ms = now_ms;
+            }
+        }
+        CircuitState::Closed => {
+            breaker.outcomes.push_back(healthy);
+            while breaker.outcomes.len() > CIRCUIT_WINDOW_SIZE {
+                breaker.outcomes.pop_front();
+            }
+            if breaker.outcomes.len() >= CIRCUIT_MIN_SAMPLES {
+                let failures = breaker.outcomes.iter().filter(|ok| !**ok).count();
+                let ratio = failures as f64 / breaker.outcomes.len() as f64;
+                if ratio >= CIRCUIT_FAILURE_RATIO {
+                    breaker.consecutive_failures += 1;
+                    breaker.state = CircuitState::Open;
+                    breaker.opened_at_ms = now_ms;
+                }
+            }
+        }
+        CircuitState::Open => {
+            // Outcomes recorded while open shouldn't normally happen since
+            // `circuit_allows_probe` gates selection, but ignore them
+            // rather than let a race double-count a failure.
+        }
+    }
+}
+
+/// Walks `fallbacks` in order and returns the first model whose gateway is
+/// currently `running && healthy && logged_in`, so callers try a model
+/// that's actually reachable instead of blindly defaulting to the head of
+/// the list.
+fn select_active_model(
+    fallbacks: &[String],
+    status_of: impl Fn(&str) -> GatewayStatus,
+) -> Option<String> {
+    fallbacks
+        .iter()
+        .find(|model| {
+            let status = status_of(model.as_str());
+            status.running && status.healthy && status.logged_in
+        })
+        .cloned()
+}
+
+/// `select_active_model`, but probes each model through a short-lived
+/// per-model cache (`state.active_model_status_cache`) so a burst of
+/// requests doesn't re-probe the whole fallback chain every time, skips
+/// models whose circuit breaker is `Open`, and logs the previous model's
+/// `last_error` whenever the selected model changes.
+fn select_active_model_cached(
+    state: &AppState,
+    fallbacks: &[String],
+    status_of: impl Fn(&str) -> GatewayStatus,
+) -> Option<String> {
+    let cached_status_of = |model: &str| -> GatewayStatus {
+        let now_ms = now_epoch_ms();
+
+        // Check the TTL cache before the circuit breaker, not after: a
+        // fresh cache hit must short-circuit without ever consulting
+        // `circuit_allows_probe`, otherwise an Open->HalfOpen transition
+        // (a one-shot permission to probe) can fire and then be thrown
+        // away on a cache hit, leaving the breaker parked in HalfOpen
+        // without ever recording a real trial outcome. Reaching the
+        // circuit check below therefore always means we're about to do a
+        // live probe, so the transition and the probe happen atomically.
+        if let Some(entry) = state.active_model_status_cache.lock().unwrap().get(model) {
+            if now_ms - entry.cached_at_ms < ACTIVE_MODEL_STATUS_TTL_MS {
+                return entry.status.clone();
+            }
+        }
+
+        if !circuit_allows_probe(state, model, now_ms) {
+            return GatewayStatus {
+                last_error: "circuit breaker open".to_string(),
+                ..Default::default()
+            };
+        }
+
+        let status = status_of(model);
+        circuit_record_outcome(state, model, status.healthy, now_ms);
+        state.active_model_status_cache.lock().unwrap().insert(
+            model.to_string(),
+            ActiveModelStatusEntry {
+                status: status.clone(),
+                cached_at_ms: now_ms,
+            },
+        );
+        status
+    };
+
+    let selected = select_active_model(fallbacks, cached_status_of);
+
+    let mut current = state.active_model_current.lock().unwrap();
+    if *current != selected {
+        if let Some(prev) = current.as_ref() {
+            let reason = state
+                .active_model_status_cache
+                .lock()
+                .unwrap()
+                .get(prev)
+                .map(|entry| entry.status.last_error.clone())
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "no error reported".to_string());
+            eprintln!(
+                "[fallback] active model changed: {} -> {:?} ({})",
+                prev, selected, reason
+            );
+        }
+        *current = selected.clone();
+    }
+
+    selected
+}
+
 fn run_openclaw_cmd(args: &[&str]) -> Result<(), String> {
     let bin = find_openclaw_bin().ok_or("OpenClaw not found in PATH or common locations")?;
     let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/ivy".to_string());
@@ -1461,14 +3403,231 @@ fn apply_fallbacks(new_list: &[String]) -> Result<(), String> {
     Ok(())
 }
 
-#[derive(Debug, Clone, Default)]
-struct ProviderAuthSummary {
-    total_profiles: usize,
-    cooldown_profiles: usize,
-    cooldown_until_ms: Option<i64>,
-    oauth_seen: bool,
-    oauth_has_valid: bool,
-    api_key_seen: bool,
+// ============================================================================
+// Live Model Health Probing (opt-in, latency-ranked fallback ordering)
+// ============================================================================
+
+/// Set to `1` to replace the static Kimi-first fallback heuristic with
+/// measured, latency-ranked ordering.
+const PROBE_MODELS_ENV_KEY: &str = "OPENCLAW_PROBE_MODELS";
+/// EWMA smoothing factor for both latency and success-rate tracking.
+const PROBE_EWMA_ALPHA: f64 = 0.3;
+const PROBE_PER_MODEL_TIMEOUT_MS: u64 = 4_000;
+/// Overall time budget for one probing pass; probing stops early (keeping
+/// whatever ranking it has so far) once this elapses.
+const PROBE_TOTAL_BUDGET_MS: u64 = 20_000;
+/// A challenger must beat the incumbent's EWMA latency by more than this
+/// fraction before it's allowed to take the top spot, so the order doesn't
+/// flap between runs on noise alone.
+const PROBE_STICKINESS_MARGIN: f64 = 0.15;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ModelProbeStat {
+    ewma_latency_ms: f64,
+    success_rate: f64,
+    samples: u32,
+}
+
+impl Default for ModelProbeStat {
+    fn default() -> Self {
+        ModelProbeStat {
+            ewma_latency_ms: 0.0,
+            success_rate: 0.0,
+            samples: 0,
+        }
+    }
+}
+
+fn model_probe_stats_path(state: &AppState) -> String {
+    format!("{}/model_probe_stats.json", state.config_path)
+}
+
+fn load_model_probe_stats(path: &str) -> HashMap<String, ModelProbeStat> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return HashMap::new(),
+    };
+    serde_json::from_str(&content).unwrap_or_default()
+}
+
+fn persist_model_probe_stats(
+    path: &str,
+    stats: &HashMap<String, ModelProbeStat>,
+) -> Result<(), String> {
+    if let Some(parent) = PathBuf::from(path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to prepare model probe stats dir: {}", e))?;
+    }
+    let payload = serde_json::to_string_pretty(stats)
+        .map_err(|e| format!("Failed to serialize model probe stats: {}", e))?;
+    fs::write(path, payload).map_err(|e| format!("Failed to persist model probe stats: {}", e))?;
+    Ok(())
+}
+
+fn update_probe_stat(
+    stats: &mut HashMap<String, ModelProbeStat>,
+    model: &str,
+    success: bool,
+    latency_ms: u64,
+) {
+    let success_sample = if success { 1.0 } else { 0.0 };
+    let entry = stats.entry(model.to_string()).or_default();
+    if entry.samples == 0 {
+        entry.ewma_latency_ms = latency_ms as f64;
+        entry.success_rate = success_sample;
+    } else {
+        entry.ewma_latency_ms =
+            PROBE_EWMA_ALPHA * latency_ms as f64 + (1.0 - PROBE_EWMA_ALPHA) * entry.ewma_latency_ms;
+        entry.success_rate =
+            PROBE_EWMA_ALPHA * success_sample + (1.0 - PROBE_EWMA_ALPHA) * entry.success_rate;
+    }
+    entry.samples += 1;
+}
+
+/// Issues one cheap bounded probe for `model`, capped at `timeout`. A
+/// timeout or spawn failure counts as a failed probe with the full timeout
+/// charged as latency, so a hung provider gets penalized rather than
+/// excluded from ranking.
+async fn probe_model_once(
+    bin: &str,
+    home: &str,
+    path_env: &str,
+    model: &str,
+    timeout: std::time::Duration,
+) -> (bool, u64) {
+    let start = std::time::Instant::now();
+    let attempt = tokio::time::timeout(
+        timeout,
+        tokio::process::Command::new(bin)
+            .args(["models", "probe", "--model", model, "--json"])
+            .env("HOME", home)
+            .env("PATH", path_env)
+            .output(),
+    )
+    .await;
+
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    match attempt {
+        Ok(Ok(output)) if output.status.success() => (true, elapsed_ms),
+        _ => (false, timeout.as_millis() as u64),
+    }
+}
+
+/// Opt-in (`OPENCLAW_PROBE_MODELS=1`) replacement for the static
+/// `compute_fallbacks_with_kimi_defaults` heuristic. Starts from that same
+/// static order, probes each runnable candidate within an overall time
+/// budget, and maintains a persisted EWMA of latency and success rate per
+/// model. The runnable set is then re-sorted by ascending EWMA latency; if
+/// that puts a different model on top, the re-sorted order is used only when
+/// the new head beats the static head by more than `PROBE_STICKINESS_MARGIN`,
+/// and otherwise the whole static order is kept. Falls back to the static order
+/// whenever `openclaw` can't be found, `models list` fails, or the time
+/// budget runs out before a single probe completes.
+async fn compute_fallbacks_with_probing(
+    state: &AppState,
+    current: Vec<String>,
+    kimi_model: &str,
+    kimi_alt_model: &str,
+    fallback_model: &str,
+) -> Vec<String> {
+    let static_order =
+        compute_fallbacks_with_kimi_defaults(current, kimi_model, kimi_alt_model, fallback_model);
+
+    let Some(bin) = find_openclaw_bin() else {
+        return static_order;
+    };
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/ivy".to_string());
+    let path_env = format!(
+        "{}:{}/.local/bin:/usr/local/bin:/opt/homebrew/bin:/usr/bin:/bin",
+        std::env::var("PATH").unwrap_or_default(),
+        home
+    );
+
+    let availability = match run_openclaw_json(&["models", "list", "--json"]) {
+        Ok(list) => models_available_map(&list),
+        Err(_) => return static_order,
+    };
+
+    let (runnable, non_runnable): (Vec<String>, Vec<String>) = static_order
+        .into_iter()
+        .partition(|m| availability.get(m).copied().unwrap_or(false));
+
+    let stats_path = model_probe_stats_path(state);
+    let mut stats = load_model_probe_stats(&stats_path);
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(PROBE_TOTAL_BUDGET_MS);
+    let per_probe_timeout = std::time::Duration::from_millis(PROBE_PER_MODEL_TIMEOUT_MS);
+
+    let mut probed_any = false;
+    for model in &runnable {
+        if std::time::Instant::now() >= deadline {
+            break;
+        }
+        let (success, latency_ms) =
+            probe_model_once(&bin, &home, &path_env, model, per_probe_timeout).await;
+        update_probe_stat(&mut stats, model, success, latency_ms);
+        probed_any = true;
+    }
+
+    if !probed_any {
+        return [runnable, non_runnable].concat();
+    }
+    let _ = persist_model_probe_stats(&stats_path, &stats);
+
+    let mut ranked = runnable.clone();
+    ranked.sort_by(|a, b| {
+        let latency_a = stats.get(a).map(|s| s.ewma_latency_ms).unwrap_or(f64::MAX);
+        let latency_b = stats.get(b).map(|s| s.ewma_latency_ms).unwrap_or(f64::MAX);
+        latency_a
+            .partial_cmp(&latency_b)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let final_runnable = match (runnable.first(), ranked.first()) {
+        (Some(incumbent), Some(challenger)) if incumbent != challenger => {
+            let incumbent_latency = stats
+                .get(incumbent)
+                .map(|s| s.ewma_latency_ms)
+                .unwrap_or(f64::MAX);
+            let challenger_latency = stats
+                .get(challenger)
+                .map(|s| s.ewma_latency_ms)
+                .unwrap_or(f64::MAX);
+            if incumbent_latency.is_finite()
+                && challenger_latency < incumbent_latency * (1.0 - PROBE_STICKINESS_MARGIN)
+            {
+                ranked
+            } else {
+                runnable
+            }
+        }
+        _ => ranked,
+    };
+
+    [final_runnable, non_runnable].concat()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProviderAuthSummary {
+    pub total_profiles: usize,
+    pub cooldown_profiles: usize,
+    pub cooldown_until_ms: Option<i64>,
+    pub oauth_seen: bool,
+    pub oauth_has_valid: bool,
+    pub api_key_seen: bool,
+}
+
+/// Merges `from` into `into`, keeping whichever summary was already present
+/// for a given provider. `provider_summaries_from_models_status` is called
+/// once per agent against what's meant to be the same global auth state, so
+/// this only needs to fill in providers the first map didn't see.
+fn merge_provider_summaries(
+    into: &mut HashMap<String, ProviderAuthSummary>,
+    from: HashMap<String, ProviderAuthSummary>,
+) {
+    for (provider, summary) in from {
+        into.entry(provider).or_insert(summary);
+    }
 }
 
 fn provider_summaries_from_models_status(
@@ -1572,6 +3731,179 @@ fn has_provider_profile(
     })
 }
 
+// ============================================================================
+// Model Provider Registry
+// ============================================================================
+
+/// What a provider can be used for. Lets call sites ask "which providers
+/// support vision?" instead of open-coding a provider-id list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    Generation,
+    Review,
+    Vision,
+    Image,
+}
+
+/// One backend OpenClaw can dispatch to. Providers are stateless descriptors
+/// (id, required env keys, default models, supported capabilities) rather
+/// than live connections, so a plain trait object per provider is enough --
+/// registering a new backend is adding one impl here instead of touching
+/// every preflight/fallback/auto-fix call site.
+pub trait ModelProvider {
+    fn id(&self) -> &'static str;
+    fn display_name(&self) -> &'static str;
+    fn required_env_keys(&self) -> &'static [&'static str];
+    fn default_models(&self) -> &'static [&'static str];
+    fn supports(&self, capability: Capability) -> bool;
+
+    /// True when any of `required_env_keys` is present and non-blank.
+    fn is_env_configured(&self, env_map: &HashMap<String, String>) -> bool {
+        self.required_env_keys().iter().any(|key| {
+            env_map
+                .get(*key)
+                .map(|v| !v.trim().is_empty())
+                .unwrap_or(false)
+        })
+    }
+
+    /// `is_env_configured`, plus credit for an API-key profile already
+    /// recorded against this provider in `openclaw models status` output.
+    fn is_configured(
+        &self,
+        env_map: &HashMap<String, String>,
+        models_status: &serde_json::Value,
+    ) -> bool {
+        self.is_env_configured(env_map)
+            || has_provider_profile(models_status, self.id(), Some("api_key"))
+    }
+}
+
+struct MoonshotProvider;
+impl ModelProvider for MoonshotProvider {
+    fn id(&self) -> &'static str {
+        "moonshot"
+    }
+    fn display_name(&self) -> &'static str {
+        "Moonshot (Kimi)"
+    }
+    fn required_env_keys(&self) -> &'static [&'static str] {
+        &["MOONSHOT_API_KEY"]
+    }
+    fn default_models(&self) -> &'static [&'static str] {
+        &["moonshot/kimi-k2.5"]
+    }
+    fn supports(&self, capability: Capability) -> bool {
+        matches!(
+            capability,
+            Capability::Generation | Capability::Review | Capability::Vision
+        )
+    }
+}
+
+struct OpenAiCodexProvider;
+impl ModelProvider for OpenAiCodexProvider {
+    fn id(&self) -> &'static str {
+        "openai-codex"
+    }
+    fn display_name(&self) -> &'static str {
+        "OpenAI Codex"
+    }
+    fn required_env_keys(&self) -> &'static [&'static str] {
+        &["OPENAI_API_KEY"]
+    }
+    fn default_models(&self) -> &'static [&'static str] {
+        &["openai-codex/gpt-5.2"]
+    }
+    fn supports(&self, capability: Capability) -> bool {
+        matches!(
+            capability,
+            Capability::Generation | Capability::Review | Capability::Vision | Capability::Image
+        )
+    }
+}
+
+struct GoogleGeminiProvider;
+impl ModelProvider for GoogleGeminiProvider {
+    fn id(&self) -> &'static str {
+        "google"
+    }
+    fn display_name(&self) -> &'static str {
+        "Google Gemini"
+    }
+    fn required_env_keys(&self) -> &'static [&'static str] {
+        &["GOOGLE_API_KEY", "GEMINI_API_KEY"]
+    }
+    fn default_models(&self) -> &'static [&'static str] {
+        &[]
+    }
+    fn supports(&self, capability: Capability) -> bool {
+        matches!(capability, Capability::Vision)
+    }
+}
+
+struct ZaiGlmProvider;
+impl ModelProvider for ZaiGlmProvider {
+    fn id(&self) -> &'static str {
+        "zai"
+    }
+    fn display_name(&self) -> &'static str {
+        "Zhipu GLM"
+    }
+    fn required_env_keys(&self) -> &'static [&'static str] {
+        &["GLM_API_KEY"]
+    }
+    fn default_models(&self) -> &'static [&'static str] {
+        &[]
+    }
+    fn supports(&self, capability: Capability) -> bool {
+        matches!(capability, Capability::Generation | Capability::Review)
+    }
+}
+
+/// Enumerates the registered providers and answers registry-wide questions
+/// (which ones are configured, which support a capability) so preflight,
+/// fallback computation, and the vision-key check can iterate it instead of
+/// each open-coding its own provider list.
+pub struct ProviderRegistry {
+    providers: Vec<Box<dyn ModelProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        ProviderRegistry {
+            providers: vec![
+                Box::new(MoonshotProvider),
+                Box::new(OpenAiCodexProvider),
+                Box::new(GoogleGeminiProvider),
+                Box::new(ZaiGlmProvider),
+            ],
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn ModelProvider> {
+        self.providers.iter().map(|p| p.as_ref())
+    }
+
+    pub fn supporting(&self, capability: Capability) -> impl Iterator<Item = &dyn ModelProvider> {
+        self.iter().filter(move |p| p.supports(capability))
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn ModelProvider> {
+        self.iter().find(|p| p.id() == id)
+    }
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn provider_registry() -> ProviderRegistry {
+    ProviderRegistry::new()
+}
+
 fn compute_agent_availability(
     agent_id: &str,
     models_status: &serde_json::Value,
@@ -1636,24 +3968,23 @@ fn compute_agent_availability(
         }
 
         let state = if available == Some(false) {
-            "unavailable"
+            RouteModelState::Unavailable
         } else if provider_all_in_cooldown {
-            "cooldown"
+            RouteModelState::Cooldown
         } else if summary.oauth_seen && oauth_expired {
-            "expired"
+            RouteModelState::Expired
         } else if available == Some(true) && auth_ok {
-            "ok"
+            RouteModelState::Ok
         } else {
-            "unknown"
-        }
-        .to_string();
+            RouteModelState::Unknown
+        };
 
         route.push(RouteModelStatus {
             model: model.to_string(),
             provider: provider.clone(),
             available,
-            state: state.clone(),
-            cooldown_until_ms: if state == "cooldown" {
+            state,
+            cooldown_until_ms: if state == RouteModelState::Cooldown {
                 summary.cooldown_until_ms
             } else {
                 None
@@ -1669,7 +4000,7 @@ fn compute_agent_availability(
 
     let first_runnable_model = route
         .iter()
-        .find(|r| r.state == "ok")
+        .find(|r| r.state == RouteModelState::Ok)
         .map(|r| r.model.clone());
     let runnable_now = first_runnable_model.is_some();
 
@@ -1704,7 +4035,7 @@ fn compute_agent_availability(
 
             if route
                 .iter()
-                .any(|r| r.provider == item.provider && r.state == "unavailable")
+                .any(|r| r.provider == item.provider && r.state == RouteModelState::Unavailable)
             {
                 blocked_reasons.push(format!("{} models are unavailable.", item.provider));
                 continue;
@@ -1728,7 +4059,7 @@ fn compute_agent_availability(
 
             if route
                 .iter()
-                .all(|r| r.provider != item.provider || r.state == "unknown")
+                .all(|r| r.provider != item.provider || r.state == RouteModelState::Unknown)
             {
                 blocked_reasons.push(format!("{} availability is unknown.", item.provider));
             }
@@ -1768,9 +4099,19 @@ fn compute_model_availability_report_inner(
     agents.insert(translator.agent_id.clone(), translator);
     agents.insert(review.agent_id.clone(), review);
 
+    let mut provider_auth = provider_summaries_from_models_status(&translator_status);
+    merge_provider_summaries(
+        &mut provider_auth,
+        provider_summaries_from_models_status(&review_status),
+    );
+
     let env_path = PathBuf::from(&state.config_path).join(".env.v4.local");
     let env_map = read_env_map(&env_path);
 
+    let registry = provider_registry();
+    // Google/Gemini keep their own env vars reported separately (both flow
+    // into the same `google` provider below), so they're checked directly
+    // rather than through the registry.
     let has_google_api_key = env_map
         .get("GOOGLE_API_KEY")
         .map(|v| !v.trim().is_empty())
@@ -1779,16 +4120,14 @@ fn compute_model_availability_report_inner(
         .get("GEMINI_API_KEY")
         .map(|v| !v.trim().is_empty())
         .unwrap_or(false);
-    let has_moonshot_api_key = env_map
-        .get("MOONSHOT_API_KEY")
-        .map(|v| !v.trim().is_empty())
-        .unwrap_or(false)
-        || has_provider_profile(&translator_status, "moonshot", Some("api_key"));
-    let has_openai_api_key = env_map
-        .get("OPENAI_API_KEY")
-        .map(|v| !v.trim().is_empty())
-        .unwrap_or(false)
-        || has_provider_profile(&translator_status, "openai-codex", Some("api_key"));
+    let has_moonshot_api_key = registry
+        .get("moonshot")
+        .map(|p| p.is_configured(&env_map, &translator_status))
+        .unwrap_or(false);
+    let has_openai_api_key = registry
+        .get("openai-codex")
+        .map(|p| p.is_configured(&env_map, &translator_status))
+        .unwrap_or(false);
 
     let vision_backend = env_map
         .get("OPENCLAW_VISION_BACKEND")
@@ -1836,13 +4175,25 @@ fn compute_model_availability_report_inner(
             })
     };
 
+    let resolved_chain = resolve_vision_backend_chain(
+        VisionBackend::parse(&backend_norm).unwrap_or(VisionBackend::Auto),
+        has_google_api_key,
+        has_gemini_api_key,
+        has_moonshot_api_key,
+        has_openai_api_key,
+    )
+    .into_iter()
+    .map(vision_backend_id)
+    .map(|id| id.to_string())
+    .collect();
+
     let glm_enabled = env_map
         .get("OPENCLAW_GLM_ENABLED")
         .map(|v| v.trim() == "1")
         .unwrap_or(false);
-    let has_glm_api_key = env_map
-        .get("GLM_API_KEY")
-        .map(|v| !v.trim().is_empty())
+    let has_glm_api_key = registry
+        .get("zai")
+        .map(|p| p.is_env_configured(&env_map))
         .unwrap_or(false);
     let has_zai_profile = has_provider_profile(&translator_status, "zai", Some("api_key"));
 
@@ -1861,23 +4212,287 @@ fn compute_model_availability_report_inner(
             has_openai_api_key,
             vision_backend,
             vision_model,
+            resolved_chain,
         },
         glm: GlmAvailability {
             glm_enabled,
             has_glm_api_key,
             has_zai_profile,
         },
+        provider_auth,
+        stale: false,
     })
 }
 
-fn run_start_script(state: &AppState, flag: &str) -> Result<String, String> {
-    let start_script = format!("{}/start.sh", state.scripts_path);
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/ivy".to_string());
+// ============================================================================
+// Availability Report Cache
+// ============================================================================
 
-    let output = Command::new("bash")
-        .arg(&start_script)
-        .arg(flag)
-        .current_dir(&state.config_path)
+/// How long a cached report is served before it's considered due for a
+/// refresh. Keeps `compute_model_availability_report_inner`'s `openclaw
+/// models list`/`models status` shell-outs from firing once per caller.
+const AVAILABILITY_CACHE_TTL_MS: i64 = 8_000;
+/// How often `run_availability_cache_refresher` checks whether the cache has
+/// expired.
+const AVAILABILITY_REFRESH_TICK_MS: u64 = 2_000;
+
+#[derive(Debug, Clone)]
+struct AvailabilityCacheEntry {
+    report: ModelAvailabilityReport,
+    cached_at_ms: i64,
+}
+
+/// Returns the cached report while it's within TTL; a cold cache, an expired
+/// entry, or a `force_refresh` request computes and caches a fresh one
+/// inline. `run_availability_cache_refresher` keeps the entry fresh in the
+/// background, but a caller that finds it expired before the refresher gets
+/// to it is not handed the older snapshot: it recomputes the report itself,
+/// so concurrent callers in that window each run their own recompute.
+fn get_cached_availability_report(
+    state: &AppState,
+    force_refresh: bool,
+) -> Result<ModelAvailabilityReport, String> {
+    if !force_refresh {
+        let now_ms = now_epoch_ms();
+        if let Some(entry) = state.availability_cache.lock().unwrap().as_ref() {
+            if now_ms - entry.cached_at_ms < AVAILABILITY_CACHE_TTL_MS {
+                return Ok(entry.report.clone());
+            }
+        }
+    }
+    refresh_availability_cache(state)
+}
+
+/// Recomputes the report and updates the cache. A failed recompute falls
+/// back to the previous snapshot marked `stale: true` rather than
+/// propagating the error, as long as one exists yet.
+fn refresh_availability_cache(state: &AppState) -> Result<ModelAvailabilityReport, String> {
+    match compute_model_availability_report_inner(state) {
+        Ok(report) => {
+            *state.availability_cache.lock().unwrap() = Some(AvailabilityCacheEntry {
+                report: report.clone(),
+                cached_at_ms: now_epoch_ms(),
+            });
+            Ok(report)
+        }
+        Err(err) => {
+            let guard = state.availability_cache.lock().unwrap();
+            match guard.as_ref() {
+                Some(entry) => {
+                    let mut stale_report = entry.report.clone();
+                    stale_report.stale = true;
+                    Ok(stale_report)
+                }
+                None => Err(err),
+            }
+        }
+    }
+}
+
+/// Spawned from `setup()`: the one place that actually calls
+/// `compute_model_availability_report_inner` on a schedule. The Tauri
+/// command, the HTTP admin API, the metrics exporter and the status watcher
+/// read through `get_cached_availability_report`, which recomputes inline
+/// only on a cold cache, an expired entry or `force_refresh`;
+/// `run_preflight_check_inner` computes its own report directly.
+async fn run_availability_cache_refresher(app: tauri::AppHandle) {
+    loop {
+        let state = app.state::<AppState>();
+        let needs_refresh = {
+            let guard = state.availability_cache.lock().unwrap();
+            match guard.as_ref() {
+                Some(entry) => now_epoch_ms() - entry.cached_at_ms >= AVAILABILITY_CACHE_TTL_MS,
+                None => true,
+            }
+        };
+        if needs_refresh {
+            let _ = refresh_availability_cache(&state);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(AVAILABILITY_REFRESH_TICK_MS)).await;
+    }
+}
+
+// ============================================================================
+// Recovery Scheduler (cooldown/backoff + automatic gateway re-login)
+// ============================================================================
+
+const RECOVERY_SCHEDULER_TICK_MS: u64 = 5_000;
+const RECOVERY_BASE_BACKOFF_MS: i64 = 5_000;
+/// Cap on the transient-failure backoff, "a few minutes" per the request.
+const RECOVERY_MAX_BACKOFF_MS: i64 = 180_000;
+const RECOVERY_JITTER_MS: i64 = 3_000;
+/// Floor between automatic `gateway_login` attempts for the same provider, so
+/// a permanently-broken credential doesn't retry in a tight loop.
+const RECOVERY_MIN_LOGIN_RETRY_MS: i64 = 60_000;
+
+#[derive(Debug, Clone, Default)]
+struct ProviderBackoffState {
+    next_check_ms: i64,
+    consecutive_failures: u32,
+    last_login_attempt_ms: Option<i64>,
+}
+
+/// Cheap, dependency-free jitter: the current time's sub-second nanoseconds
+/// modulo `max_ms`. Good enough to keep several providers recovering on the
+/// same schedule from re-checking in lockstep.
+fn jitter_ms(max_ms: i64) -> i64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as i64)
+        .unwrap_or(0);
+    nanos % max_ms.max(1)
+}
+
+fn next_backoff_delay_ms(consecutive_failures: u32) -> i64 {
+    let exponent = consecutive_failures.saturating_sub(1).min(20);
+    RECOVERY_BASE_BACKOFF_MS
+        .saturating_mul(1i64 << exponent)
+        .min(RECOVERY_MAX_BACKOFF_MS)
+}
+
+/// Reviews one provider's current auth summary and either resets its backoff
+/// state (provider recovered), waits for its scheduled re-check, or acts:
+/// reschedules around an explicit `cooldown_until_ms`, backs off
+/// exponentially for a cooldown with no explicit deadline, or fires an
+/// automatic `gateway_login` when OAuth looks expired/missing. Every
+/// automatic login attempt is recorded via `best_effort_audit_operation`
+/// with `action: "auto_recover"`.
+fn run_provider_recovery_tick(
+    state: &AppState,
+    provider: &str,
+    summary: &ProviderAuthSummary,
+    now_ms: i64,
+) {
+    let provider_all_in_cooldown =
+        summary.total_profiles > 0 && summary.cooldown_profiles >= summary.total_profiles;
+    let oauth_needs_login = summary.oauth_seen && !summary.oauth_has_valid;
+
+    if !provider_all_in_cooldown && !oauth_needs_login {
+        state.provider_backoff.lock().unwrap().remove(provider);
+        return;
+    }
+
+    let due_ms = {
+        let mut backoff = state.provider_backoff.lock().unwrap();
+        backoff.entry(provider.to_string()).or_default().next_check_ms
+    };
+    // `0` means this provider has never been scheduled before -- act now.
+    if due_ms != 0 && now_ms < due_ms {
+        return;
+    }
+
+    if oauth_needs_login {
+        let last_attempt = state
+            .provider_backoff
+            .lock()
+            .unwrap()
+            .get(provider)
+            .and_then(|entry| entry.last_login_attempt_ms);
+        let can_retry_login =
+            last_attempt.map_or(true, |at| now_ms - at >= RECOVERY_MIN_LOGIN_RETRY_MS);
+        if can_retry_login {
+            let login_result =
+                gateway_login_inner(state, Some(provider.to_string()), Some(false), None);
+            let succeeded = login_result
+                .as_ref()
+                .map(|status| status.logged_in)
+                .unwrap_or(false);
+            best_effort_audit_operation(
+                state,
+                AuditOperationPayload {
+                    source: "scheduler".to_string(),
+                    action: "auto_recover".to_string(),
+                    status: if succeeded { "success" } else { "failed" }.to_string(),
+                    summary: format!(
+                        "auto_recover:{} automatic gateway_login {}",
+                        provider,
+                        if succeeded { "succeeded" } else { "failed" }
+                    ),
+                    detail: Some(serde_json::json!({
+                        "provider": provider,
+                        "result": login_result.as_ref().err(),
+                    })),
+                    ..AuditOperationPayload::default()
+                },
+            );
+
+            let mut backoff = state.provider_backoff.lock().unwrap();
+            let entry = backoff.entry(provider.to_string()).or_default();
+            entry.last_login_attempt_ms = Some(now_ms);
+            if succeeded {
+                entry.consecutive_failures = 0;
+                entry.next_check_ms = now_ms + RECOVERY_BASE_BACKOFF_MS;
+            } else {
+                entry.consecutive_failures += 1;
+                entry.next_check_ms =
+                    now_ms + next_backoff_delay_ms(entry.consecutive_failures) + jitter_ms(RECOVERY_JITTER_MS);
+            }
+            return;
+        }
+    }
+
+    let mut backoff = state.provider_backoff.lock().unwrap();
+    let entry = backoff.entry(provider.to_string()).or_default();
+    if let Some(until_ms) = summary.cooldown_until_ms {
+        entry.next_check_ms = until_ms + jitter_ms(RECOVERY_JITTER_MS);
+        entry.consecutive_failures = 0;
+    } else {
+        entry.consecutive_failures += 1;
+        entry.next_check_ms =
+            now_ms + next_backoff_delay_ms(entry.consecutive_failures) + jitter_ms(RECOVERY_JITTER_MS);
+    }
+}
+
+/// Spawned from `setup()`: ticks the recovery scheduler, emitting an
+/// `availability-recovery` event for any agent whose `first_runnable_model`
+/// just became populated, and driving `run_provider_recovery_tick` for every
+/// provider currently reported.
+async fn run_recovery_scheduler(app: tauri::AppHandle) {
+    let mut was_runnable: HashMap<String, bool> = HashMap::new();
+
+    loop {
+        let state = app.state::<AppState>();
+        if let Ok(report) = get_cached_availability_report(&state, false) {
+            let now_ms = now_epoch_ms();
+
+            let mut agent_ids: Vec<&String> = report.agents.keys().collect();
+            agent_ids.sort();
+            for agent_id in agent_ids {
+                let availability = &report.agents[agent_id];
+                let runnable = availability.first_runnable_model.is_some();
+                let previously_runnable = was_runnable.get(agent_id).copied().unwrap_or(false);
+                if runnable && !previously_runnable {
+                    let _ = app.emit(
+                        "availability-recovery",
+                        serde_json::json!({
+                            "agent": agent_id,
+                            "first_runnable_model": availability.first_runnable_model,
+                            "at": now_ms,
+                        }),
+                    );
+                }
+                was_runnable.insert(agent_id.clone(), runnable);
+            }
+
+            let mut providers: Vec<&String> = report.provider_auth.keys().collect();
+            providers.sort();
+            for provider in providers {
+                run_provider_recovery_tick(&state, provider, &report.provider_auth[provider], now_ms);
+            }
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(RECOVERY_SCHEDULER_TICK_MS)).await;
+    }
+}
+
+fn run_start_script(state: &AppState, flag: &str) -> Result<String, String> {
+    let start_script = format!("{}/start.sh", state.scripts_path);
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/ivy".to_string());
+
+    let output = Command::new("bash")
+        .arg(&start_script)
+        .arg(flag)
+        .current_dir(&state.config_path)
         .env("HOME", &home)
         .env(
             "PATH",
@@ -1924,33 +4539,29 @@ fn audit_operation(
     audit_operation_inner(&state, &payload)
 }
 
-#[tauri::command]
-fn gateway_status(state: State<'_, AppState>) -> Result<GatewayStatus, String> {
-    let out = run_dispatcher_json(&state, &["gateway-status"])?;
+fn gateway_status_inner(state: &AppState) -> Result<GatewayStatus, String> {
+    let out = run_dispatcher_json(state, &["gateway-status"])?;
     Ok(parse_gateway_status(&out))
 }
 
-#[tauri::command]
-fn gateway_start(state: State<'_, AppState>) -> Result<GatewayStatus, String> {
-    let out = run_dispatcher_json(&state, &["gateway-start"])?;
+fn gateway_start_inner(state: &AppState) -> Result<GatewayStatus, String> {
+    let out = run_dispatcher_json(state, &["gateway-start"])?;
     if !out.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
         return Err(format!("gateway-start failed: {}", out));
     }
     Ok(parse_gateway_status(&out))
 }
 
-#[tauri::command]
-fn gateway_stop(state: State<'_, AppState>) -> Result<GatewayStatus, String> {
-    let out = run_dispatcher_json(&state, &["gateway-stop"])?;
+fn gateway_stop_inner(state: &AppState) -> Result<GatewayStatus, String> {
+    let out = run_dispatcher_json(state, &["gateway-stop"])?;
     if !out.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
         return Err(format!("gateway-stop failed: {}", out));
     }
     Ok(parse_gateway_status(&out))
 }
 
-#[tauri::command]
-fn gateway_login(
-    state: State<'_, AppState>,
+fn gateway_login_inner(
+    state: &AppState,
     provider: Option<String>,
     interactive_login: Option<bool>,
     timeout_seconds: Option<u32>,
@@ -1971,20 +4582,45 @@ fn gateway_login(
         args.push(ts.to_string());
     }
     let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    let out = run_dispatcher_json(&state, &args_ref)?;
+    let out = run_dispatcher_json(state, &args_ref)?;
     if !out.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
         return Err(format!("gateway-login failed: {}", out));
     }
     Ok(parse_gateway_status(&out))
 }
 
+#[tauri::command]
+fn gateway_status(state: State<'_, AppState>) -> Result<GatewayStatus, String> {
+    gateway_status_inner(&state)
+}
+
+#[tauri::command]
+fn gateway_start(state: State<'_, AppState>) -> Result<GatewayStatus, String> {
+    gateway_start_inner(&state)
+}
+
+#[tauri::command]
+fn gateway_stop(state: State<'_, AppState>) -> Result<GatewayStatus, String> {
+    gateway_stop_inner(&state)
+}
+
+#[tauri::command]
+fn gateway_login(
+    state: State<'_, AppState>,
+    provider: Option<String>,
+    interactive_login: Option<bool>,
+    timeout_seconds: Option<u32>,
+) -> Result<GatewayStatus, String> {
+    gateway_login_inner(&state, provider, interactive_login, timeout_seconds)
+}
+
 // ============================================================================
 // Service Management Commands
 // ============================================================================
 
 #[tauri::command]
 async fn get_service_status(state: State<'_, AppState>) -> Result<Vec<ServiceStatus>, String> {
-    get_service_status_inner(&state)
+    get_service_status_inner(&state).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -1992,7 +4628,7 @@ async fn start_all_services(state: State<'_, AppState>) -> Result<Vec<ServiceSta
     let result = async {
         start_services_inner(&state)?;
         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-        get_service_status_inner(&state)
+        get_service_status_inner(&state).map_err(|e| e.to_string())
     }
     .await;
     match &result {
@@ -2059,7 +4695,7 @@ async fn restart_all_services(state: State<'_, AppState>) -> Result<Vec<ServiceS
         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
         start_services_inner(&state)?;
         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-        get_service_status_inner(&state)
+        get_service_status_inner(&state).map_err(|e| e.to_string())
     }
     .await;
     match &result {
@@ -2101,39 +4737,52 @@ fn service_flag(service_id: &str, action: &str) -> Result<&'static str, String>
     }
 }
 
-#[tauri::command]
-async fn start_service(
-    service_id: String,
-    state: State<'_, AppState>,
+/// Shared body behind `start_service`/`stop_service`/`restart_service` and
+/// their HTTP admin API equivalents. `source` is forwarded to the audit log
+/// verbatim so entries triggered over HTTP show up as `"http"` instead of
+/// `"tauri"`.
+async fn perform_service_action_inner(
+    state: &AppState,
+    service_id: &str,
+    action: &str,
+    source: &str,
 ) -> Result<Vec<ServiceStatus>, String> {
-    let service_name = service_id.clone();
+    let settle_secs = if action == "restart" { 2 } else { 1 };
     let result = async {
-        let flag = service_flag(service_id.trim(), "start")?;
-        run_start_script(&state, flag)?;
-        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-        get_service_status_inner(&state)
+        let flag = service_flag(service_id.trim(), action)?;
+        run_start_script(state, flag)?;
+        tokio::time::sleep(std::time::Duration::from_secs(settle_secs)).await;
+        get_service_status_inner(state).map_err(|e| e.to_string())
     }
     .await;
+    let audit_action = format!("service_{}", action);
+    let counter_key = format!("{}_{}", action, if result.is_ok() { "success" } else { "failed" });
+    *state
+        .service_action_counts
+        .lock()
+        .unwrap()
+        .entry(counter_key)
+        .or_insert(0) += 1;
     match &result {
         Ok(services) => best_effort_audit_operation(
-            &state,
+            state,
             AuditOperationPayload {
-                source: "tauri".to_string(),
-                action: "service_start".to_string(),
+                source: source.to_string(),
+                action: audit_action.clone(),
                 status: "success".to_string(),
-                summary: format!("start_service:{} completed", service_name),
-                detail: Some(serde_json::json!({ "service": service_name, "services": services })),
+                summary: format!("{}:{} completed", audit_action, service_id),
+                detail: Some(serde_json::json!({ "service": service_id, "services": services })),
                 ..AuditOperationPayload::default()
             },
         ),
         Err(err) => best_effort_audit_operation(
-            &state,
+            state,
             AuditOperationPayload {
-                source: "tauri".to_string(),
-                action: "service_start".to_string(),
+                source: source.to_string(),
+                action: audit_action.clone(),
                 status: "failed".to_string(),
-                summary: format!("start_service:{} failed", service_name),
-                detail: Some(serde_json::json!({ "service": service_name, "error": err })),
+                summary: format!("{}:{} failed", audit_action, service_id),
+                detail: Some(serde_json::json!({ "service": service_id, "error": err })),
                 ..AuditOperationPayload::default()
             },
         ),
@@ -2141,44 +4790,20 @@ async fn start_service(
     result
 }
 
+#[tauri::command]
+async fn start_service(
+    service_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ServiceStatus>, String> {
+    perform_service_action_inner(&state, &service_id, "start", "tauri").await
+}
+
 #[tauri::command]
 async fn stop_service(
     service_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<ServiceStatus>, String> {
-    let service_name = service_id.clone();
-    let result = async {
-        let flag = service_flag(service_id.trim(), "stop")?;
-        run_start_script(&state, flag)?;
-        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-        get_service_status_inner(&state)
-    }
-    .await;
-    match &result {
-        Ok(services) => best_effort_audit_operation(
-            &state,
-            AuditOperationPayload {
-                source: "tauri".to_string(),
-                action: "service_stop".to_string(),
-                status: "success".to_string(),
-                summary: format!("stop_service:{} completed", service_name),
-                detail: Some(serde_json::json!({ "service": service_name, "services": services })),
-                ..AuditOperationPayload::default()
-            },
-        ),
-        Err(err) => best_effort_audit_operation(
-            &state,
-            AuditOperationPayload {
-                source: "tauri".to_string(),
-                action: "service_stop".to_string(),
-                status: "failed".to_string(),
-                summary: format!("stop_service:{} failed", service_name),
-                detail: Some(serde_json::json!({ "service": service_name, "error": err })),
-                ..AuditOperationPayload::default()
-            },
-        ),
-    }
-    result
+    perform_service_action_inner(&state, &service_id, "stop", "tauri").await
 }
 
 #[tauri::command]
@@ -2186,39 +4811,7 @@ async fn restart_service(
     service_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<ServiceStatus>, String> {
-    let service_name = service_id.clone();
-    let result = async {
-        let flag = service_flag(service_id.trim(), "restart")?;
-        run_start_script(&state, flag)?;
-        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-        get_service_status_inner(&state)
-    }
-    .await;
-    match &result {
-        Ok(services) => best_effort_audit_operation(
-            &state,
-            AuditOperationPayload {
-                source: "tauri".to_string(),
-                action: "service_restart".to_string(),
-                status: "success".to_string(),
-                summary: format!("restart_service:{} completed", service_name),
-                detail: Some(serde_json::json!({ "service": service_name, "services": services })),
-                ..AuditOperationPayload::default()
-            },
-        ),
-        Err(err) => best_effort_audit_operation(
-            &state,
-            AuditOperationPayload {
-                source: "tauri".to_string(),
-                action: "service_restart".to_string(),
-                status: "failed".to_string(),
-                summary: format!("restart_service:{} failed", service_name),
-                detail: Some(serde_json::json!({ "service": service_name, "error": err })),
-                ..AuditOperationPayload::default()
-            },
-        ),
-    }
-    result
+    perform_service_action_inner(&state, &service_id, "restart", "tauri").await
 }
 
 // ============================================================================
@@ -2226,7 +4819,7 @@ async fn restart_service(
 // ============================================================================
 
 #[tauri::command]
-fn auto_fix_preflight(state: State<'_, AppState>) -> Result<Vec<PreflightCheck>, String> {
+async fn auto_fix_preflight(state: State<'_, AppState>) -> Result<Vec<PreflightCheck>, String> {
     // Try to create venv if missing
     let venv_path = format!("{}/.venv", state.config_path);
     if !PathBuf::from(&venv_path).exists() {
@@ -2246,32 +4839,18 @@ fn auto_fix_preflight(state: State<'_, AppState>) -> Result<Vec<PreflightCheck>,
             .status();
     }
 
-    // Try to create .env.v4.local template if missing
+    // Try to create .env.v4.local template if missing. Generated from
+    // `TranslationConfig`'s own defaults so the template can't drift from
+    // what `load_translation_config` actually parses.
     let env_path = format!("{}/.env.v4.local", state.config_path);
     if !PathBuf::from(&env_path).exists() {
-        let template = r#"# Translation system configuration
-	V4_WORK_ROOT=
-	V4_KB_ROOT=
-	OPENCLAW_STRICT_ROUTER=0
-	OPENCLAW_REQUIRE_NEW=0
-	OPENCLAW_RAG_BACKEND=local
-	OPENCLAW_KIMI_MODEL=moonshot/kimi-k2.5
-	OPENCLAW_KIMI_ALT_MODEL=kimi-coding/k2p5
-	OPENCLAW_PRIMARY_MODEL=openai-codex/gpt-5.2
-	OPENCLAW_FALLBACK_MODEL=kimi-coding/k2p5
-	OPENCLAW_IMAGE_MODEL=openai-codex/gpt-5.2
-	# Vision QA backend: auto | gemini | moonshot | openai
-	OPENCLAW_VISION_BACKEND=openai
-	# Optional model overrides:
-	# OPENCLAW_GEMINI_VISION_MODEL=gemini-3-pro
-	# OPENCLAW_MOONSHOT_VISION_MODEL=moonshot/kimi-k2.5
-	# OPENCLAW_OPENAI_VISION_MODEL=openai-codex/gpt-5.2
-	"#;
+        let template = render_translation_config_template();
         let _ = fs::write(&env_path, template);
     }
 
-    // Best-effort: ensure Kimi fallback is before any GLM fallbacks.
-    // This enables failover to Kimi when Codex/Gemini are unavailable, without live probing.
+    // Best-effort: ensure Kimi fallback is before any GLM fallbacks (or, with
+    // OPENCLAW_PROBE_MODELS=1, a measured latency ranking of the runnable set).
+    // This enables failover to Kimi when Codex/Gemini are unavailable.
     if let Some(bin) = find_openclaw_bin() {
         let home = std::env::var("HOME").unwrap_or_else(|_| "/Users/ivy".to_string());
         let health_ok = Command::new(&bin)
@@ -2291,11 +4870,22 @@ fn auto_fix_preflight(state: State<'_, AppState>) -> Result<Vec<PreflightCheck>,
 
         if health_ok {
             let env_map = read_env_map(&PathBuf::from(&state.config_path).join(".env.v4.local"));
+            let registry = provider_registry();
+            let moonshot_default = registry
+                .get("moonshot")
+                .and_then(|p| p.default_models().first())
+                .copied()
+                .unwrap_or("moonshot/kimi-k2.5");
+            let openai_codex_default = registry
+                .get("openai-codex")
+                .and_then(|p| p.default_models().first())
+                .copied()
+                .unwrap_or("openai-codex/gpt-5.2");
             let kimi_model = env_map
                 .get("OPENCLAW_KIMI_MODEL")
                 .cloned()
                 .filter(|v| !v.trim().is_empty())
-                .unwrap_or_else(|| "moonshot/kimi-k2.5".to_string());
+                .unwrap_or_else(|| moonshot_default.to_string());
             let kimi_alt_model = env_map
                 .get("OPENCLAW_KIMI_ALT_MODEL")
                 .cloned()
@@ -2305,7 +4895,7 @@ fn auto_fix_preflight(state: State<'_, AppState>) -> Result<Vec<PreflightCheck>,
                 .get("OPENCLAW_PRIMARY_MODEL")
                 .cloned()
                 .filter(|v| !v.trim().is_empty())
-                .unwrap_or_else(|| "openai-codex/gpt-5.2".to_string());
+                .unwrap_or_else(|| openai_codex_default.to_string());
             let image_model = env_map
                 .get("OPENCLAW_IMAGE_MODEL")
                 .cloned()
@@ -2341,12 +4931,27 @@ fn auto_fix_preflight(state: State<'_, AppState>) -> Result<Vec<PreflightCheck>,
                     })
                     .unwrap_or_default();
 
-                let desired = compute_fallbacks_with_kimi_defaults(
-                    current.clone(),
-                    &kimi_model,
-                    &kimi_alt_model,
-                    &fallback_model,
-                );
+                let probing_enabled = env_map
+                    .get(PROBE_MODELS_ENV_KEY)
+                    .map(|v| v.trim() == "1")
+                    .unwrap_or(false);
+                let desired = if probing_enabled {
+                    compute_fallbacks_with_probing(
+                        &state,
+                        current.clone(),
+                        &kimi_model,
+                        &kimi_alt_model,
+                        &fallback_model,
+                    )
+                    .await
+                } else {
+                    compute_fallbacks_with_kimi_defaults(
+                        current.clone(),
+                        &kimi_model,
+                        &kimi_alt_model,
+                        &fallback_model,
+                    )
+                };
                 if desired != current {
                     let _ = apply_fallbacks(&desired);
                 }
@@ -2383,6 +4988,7 @@ fn auto_fix_preflight(state: State<'_, AppState>) -> Result<Vec<PreflightCheck>,
 }
 
 fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
+    let otel_start = otel_now_unix_nano();
     let mut checks = Vec::new();
 
     // Python check
@@ -2396,9 +5002,9 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         name: "Python".to_string(),
         key: "python".to_string(),
         status: if python_ok {
-            "pass".to_string()
+            PreflightStatus::Pass
         } else {
-            "blocker".to_string()
+            PreflightStatus::Blocker
         },
         message: if python_ok {
             "Python 3 available".to_string()
@@ -2415,9 +5021,9 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         name: "venv".to_string(),
         key: "venv".to_string(),
         status: if venv_exists {
-            "pass".to_string()
+            PreflightStatus::Pass
         } else {
-            "blocker".to_string()
+            PreflightStatus::Blocker
         },
         message: if venv_exists {
             "Virtual environment exists".to_string()
@@ -2434,9 +5040,9 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         name: "requirements".to_string(),
         key: "requirements".to_string(),
         status: if req_exists {
-            "pass".to_string()
+            PreflightStatus::Pass
         } else {
-            "warning".to_string()
+            PreflightStatus::Warning
         },
         message: if req_exists {
             "requirements.txt found".to_string()
@@ -2453,9 +5059,9 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         name: ".env.v4.local".to_string(),
         key: "env".to_string(),
         status: if env_exists {
-            "pass".to_string()
+            PreflightStatus::Pass
         } else {
-            "blocker".to_string()
+            PreflightStatus::Blocker
         },
         message: if env_exists {
             "Config file exists".to_string()
@@ -2464,6 +5070,31 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         },
     });
 
+    // Typed config validation (unknown OPENCLAW_VISION_BACKEND values, etc.)
+    if env_exists {
+        let env_path_buf = PathBuf::from(&env_path);
+        checks.push(match load_translation_config(&env_path_buf) {
+            Ok((_, warnings)) if warnings.is_empty() => PreflightCheck {
+                name: "Config validation".to_string(),
+                key: "config_valid".to_string(),
+                status: PreflightStatus::Pass,
+                message: ".env.v4.local parsed and validated".to_string(),
+            },
+            Ok((_, warnings)) => PreflightCheck {
+                name: "Config validation".to_string(),
+                key: "config_valid".to_string(),
+                status: PreflightStatus::Warning,
+                message: warnings.join("; "),
+            },
+            Err(err) => PreflightCheck {
+                name: "Config validation".to_string(),
+                key: "config_valid".to_string(),
+                status: PreflightStatus::Blocker,
+                message: err.to_string(),
+            },
+        });
+    }
+
     // Parse env once for mode-dependent checks.
     let env_map = read_env_map(&PathBuf::from(&state.config_path).join(".env.v4.local"));
     let web_gateway_enabled = env_map
@@ -2511,11 +5142,11 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         name: "OpenClaw".to_string(),
         key: "openclaw".to_string(),
         status: if openclaw_ok {
-            "pass".to_string()
+            PreflightStatus::Pass
         } else if web_gateway_enabled {
-            "warning".to_string()
+            PreflightStatus::Warning
         } else {
-            "blocker".to_string()
+            PreflightStatus::Blocker
         },
         message: if openclaw_ok {
             "OpenClaw is running".to_string()
@@ -2535,21 +5166,22 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         .get("GEMINI_API_KEY")
         .map(|v| !v.trim().is_empty())
         .unwrap_or(false);
-    let vision_has_moonshot_env = env_map
-        .get("MOONSHOT_API_KEY")
-        .map(|v| !v.trim().is_empty())
+    let registry = provider_registry();
+    let vision_has_moonshot_env = registry
+        .get("moonshot")
+        .map(|p| p.is_env_configured(&env_map))
         .unwrap_or(false);
-    let vision_has_openai_env = env_map
-        .get("OPENAI_API_KEY")
-        .map(|v| !v.trim().is_empty())
+    let vision_has_openai_env = registry
+        .get("openai-codex")
+        .map(|p| p.is_env_configured(&env_map))
         .unwrap_or(false);
     let glm_enabled = env_map
         .get("OPENCLAW_GLM_ENABLED")
         .map(|v| v.trim() == "1")
         .unwrap_or(false);
-    let glm_has_key = env_map
-        .get("GLM_API_KEY")
-        .map(|v| !v.trim().is_empty())
+    let glm_has_key = registry
+        .get("zai")
+        .map(|p| p.is_env_configured(&env_map))
         .unwrap_or(false);
 
     let report = if openclaw_ok {
@@ -2571,31 +5203,31 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
     // translator-core model route (required)
     let (translator_status, translator_msg) = if web_gateway_enabled {
         (
-            "warning",
+            PreflightStatus::Warning,
             "Skipped in web gateway mode (generation/review providers are configured via OPENCLAW_WEB_LLM_*).".to_string(),
         )
     } else {
         match report.as_ref().and_then(|r| r.agents.get("translator-core")) {
             Some(a) if a.runnable_now => (
-                "pass",
+                PreflightStatus::Pass,
                 format!(
                     "Runnable. First usable model: {}. (Inspect: openclaw models status --agent translator-core --json)",
                     a.first_runnable_model.clone().unwrap_or_else(|| "unknown".to_string())
                 ),
             ),
             Some(a) => (
-                "blocker",
+                PreflightStatus::Blocker,
                 format!(
                     "Blocked. {} (Inspect: openclaw models status --agent translator-core --json; Fix auth: openclaw models auth login --provider openai-codex)",
                     a.blocked_reasons.join(" ")
                 ),
             ),
             None if openclaw_ok => (
-                "blocker",
+                PreflightStatus::Blocker,
                 "Could not evaluate model availability (openclaw models status/list failed). Try: openclaw models status --agent translator-core --json".to_string(),
             ),
             None => (
-                "blocker",
+                PreflightStatus::Blocker,
                 "OpenClaw not running; cannot evaluate translator-core models. Try: openclaw gateway --force".to_string(),
             ),
         }
@@ -2603,38 +5235,38 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
     checks.push(PreflightCheck {
         name: "Models (translator-core)".to_string(),
         key: "models_translator_core".to_string(),
-        status: translator_status.to_string(),
+        status: translator_status,
         message: translator_msg,
     });
 
     // review-core model route (optional-ish: warnings)
     let (review_status, review_msg) = if web_gateway_enabled {
         (
-            "warning",
+            PreflightStatus::Warning,
             "Skipped in web gateway mode (review providers are configured via OPENCLAW_WEB_LLM_*).".to_string(),
         )
     } else {
         match report.as_ref().and_then(|r| r.agents.get("review-core")) {
             Some(a) if a.runnable_now => (
-                "pass",
+                PreflightStatus::Pass,
                 format!(
                     "Runnable. First usable model: {}. (Inspect: openclaw models status --agent review-core --json)",
                     a.first_runnable_model.clone().unwrap_or_else(|| "unknown".to_string())
                 ),
             ),
             Some(a) => (
-                "warning",
+                PreflightStatus::Warning,
                 format!(
                     "Not runnable. {} (Inspect: openclaw models status --agent review-core --json)",
                     a.blocked_reasons.join(" ")
                 ),
             ),
             None if openclaw_ok => (
-                "warning",
+                PreflightStatus::Warning,
                 "Could not evaluate review-core model availability. Try: openclaw models status --agent review-core --json".to_string(),
             ),
             None => (
-                "warning",
+                PreflightStatus::Warning,
                 "OpenClaw not running; cannot evaluate review-core models.".to_string(),
             ),
         }
@@ -2642,29 +5274,38 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
     checks.push(PreflightCheck {
         name: "Models (review-core)".to_string(),
         key: "models_review_core".to_string(),
-        status: review_status.to_string(),
+        status: review_status,
         message: review_msg,
     });
 
     // Vision QA keys (Format QA)
+    let vision_any_configured =
+        vision_has_google || vision_has_gemini || vision_has_moonshot || vision_has_openai;
+    let vision_chain_suffix = report
+        .as_ref()
+        .filter(|_| vision_any_configured)
+        .map(|r| {
+            let backend = VisionBackend::parse(r.vision.vision_backend.as_deref().unwrap_or("auto"))
+                .unwrap_or(VisionBackend::Auto);
+            let chain: Vec<VisionBackend> = r
+                .vision
+                .resolved_chain
+                .iter()
+                .filter_map(|id| VisionBackend::parse(id).ok())
+                .collect();
+            format!(" ({})", format_vision_backend_chain(backend, &chain))
+        })
+        .unwrap_or_default();
     checks.push(PreflightCheck {
         name: "Vision QA Keys".to_string(),
         key: "vision_keys".to_string(),
-        status: if vision_has_google
-            || vision_has_gemini
-            || vision_has_moonshot
-            || vision_has_openai
-        {
-            "pass".to_string()
+        status: if vision_any_configured {
+            PreflightStatus::Pass
         } else {
-            "warning".to_string()
+            PreflightStatus::Warning
         },
-        message: if vision_has_google
-            || vision_has_gemini
-            || vision_has_moonshot
-            || vision_has_openai
-        {
-            "Vision QA credentials configured.".to_string()
+        message: if vision_any_configured {
+            format!("Vision QA credentials configured.{}", vision_chain_suffix)
         } else {
             "Missing vision credentials (Gemini, Moonshot, or OpenAI); Format QA will be skipped."
                 .to_string()
@@ -2677,7 +5318,11 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         .map(|r| r.glm.has_zai_profile)
         .unwrap_or(false);
     let glm_ok = !glm_enabled || glm_has_key || has_zai_profile;
-    let glm_status = if glm_ok { "pass" } else { "warning" };
+    let glm_status = if glm_ok {
+        PreflightStatus::Pass
+    } else {
+        PreflightStatus::Warning
+    };
     let glm_message = if !glm_enabled {
         "GLM disabled (OPENCLAW_GLM_ENABLED!=1).".to_string()
     } else if glm_has_key || has_zai_profile {
@@ -2688,7 +5333,7 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
     checks.push(PreflightCheck {
         name: "GLM".to_string(),
         key: "glm".to_string(),
-        status: glm_status.to_string(),
+        status: glm_status,
         message: glm_message,
     });
 
@@ -2703,9 +5348,9 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         name: "LibreOffice".to_string(),
         key: "libreoffice".to_string(),
         status: if libreoffice_ok {
-            "pass".to_string()
+            PreflightStatus::Pass
         } else {
-            "warning".to_string()
+            PreflightStatus::Warning
         },
         message: if libreoffice_ok {
             "LibreOffice available".to_string()
@@ -2714,9 +5359,61 @@ fn run_preflight_check_inner(state: &AppState) -> Vec<PreflightCheck> {
         },
     });
 
+    export_otel_preflight_span(state, otel_start, &checks);
+
     checks
 }
 
+/// One span per preflight run, with a child event per `PreflightCheck`
+/// carrying its key/status/message, plus pass/warning/blocker tallies as
+/// span attributes. No-op when `OPENCLAW_OTEL_EXPORTER_OTLP_ENDPOINT` isn't
+/// set.
+fn export_otel_preflight_span(state: &AppState, start_unix_nano: u128, checks: &[PreflightCheck]) {
+    if otel_otlp_endpoint(state).is_none() {
+        return;
+    }
+    let pass_count = checks
+        .iter()
+        .filter(|c| c.status == PreflightStatus::Pass)
+        .count();
+    let warning_count = checks
+        .iter()
+        .filter(|c| c.status == PreflightStatus::Warning)
+        .count();
+    let blocker_count = checks
+        .iter()
+        .filter(|c| c.status == PreflightStatus::Blocker)
+        .count();
+    let now = otel_now_unix_nano();
+
+    export_otel_span(
+        state,
+        OtelSpan {
+            name: "preflight.run".to_string(),
+            start_unix_nano,
+            end_unix_nano: now,
+            status_ok: blocker_count == 0,
+            attributes: serde_json::json!({
+                "checks.pass": pass_count as i64,
+                "checks.warning": warning_count as i64,
+                "checks.blocker": blocker_count as i64,
+            }),
+            events: checks
+                .iter()
+                .map(|c| OtelSpanEvent {
+                    name: format!("preflight.check.{}", c.key),
+                    time_unix_nano: now,
+                    attributes: serde_json::json!({
+                        "key": c.key,
+                        "status": format!("{:?}", c.status),
+                        "message": c.message,
+                    }),
+                })
+                .collect(),
+        },
+    );
+}
+
 fn step_result(
     phase: &str,
     status: &str,
@@ -2731,6 +5428,7 @@ fn step_result(
         hint_action,
         started_at,
         ended_at: now_iso(),
+        duration_ms: None,
     }
 }
 
@@ -2778,6 +5476,7 @@ async fn stop_openclaw_component(
     name: String,
     state: State<'_, AppState>,
 ) -> Result<serde_json::Value, String> {
+    let otel_start = otel_now_unix_nano();
     let target = name.trim().to_lowercase();
     let result = match target.as_str() {
         "gateway" => {
@@ -2794,6 +5493,17 @@ async fn stop_openclaw_component(
         }
         other => Err(format!("Unsupported component name: {}", other)),
     };
+    export_otel_span(
+        &state,
+        OtelSpan {
+            name: "component.stop".to_string(),
+            start_unix_nano: otel_start,
+            end_unix_nano: otel_now_unix_nano(),
+            status_ok: result.is_ok(),
+            attributes: serde_json::json!({ "component": target }),
+            events: Vec::new(),
+        },
+    );
     match &result {
         Ok(val) => best_effort_audit_operation(
             &state,
@@ -2826,6 +5536,7 @@ async fn restart_openclaw_component(
     name: String,
     state: State<'_, AppState>,
 ) -> Result<serde_json::Value, String> {
+    let otel_start = otel_now_unix_nano();
     let target = name.trim().to_lowercase();
     let result = match target.as_str() {
         "gateway" => {
@@ -2847,6 +5558,17 @@ async fn restart_openclaw_component(
         }
         other => Err(format!("Unsupported component name: {}", other)),
     };
+    export_otel_span(
+        &state,
+        OtelSpan {
+            name: "component.restart".to_string(),
+            start_unix_nano: otel_start,
+            end_unix_nano: otel_now_unix_nano(),
+            status_ok: result.is_ok(),
+            attributes: serde_json::json!({ "component": target }),
+            events: Vec::new(),
+        },
+    );
     match &result {
         Ok(val) => best_effort_audit_operation(
             &state,
@@ -2892,20 +5614,28 @@ fn get_startup_snapshot(state: State<'_, AppState>) -> Result<StartupSnapshot, S
 async fn start_openclaw_v2(
     payload: Option<StartOpenclawPayload>,
     state: State<'_, AppState>,
-) -> Result<Vec<StartupStepResult>, String> {
+) -> Result<Vec<StartupStepResult>, AppError> {
     start_openclaw_v2_inner(&state, &payload.unwrap_or_default())
 }
 
 fn start_openclaw_v2_inner(
     state: &AppState,
     payload: &StartOpenclawPayload,
-) -> Result<Vec<StartupStepResult>, String> {
+) -> Result<Vec<StartupStepResult>, AppError> {
+    let otel_start = otel_now_unix_nano();
     let force_restart = payload.force_restart.unwrap_or(false);
     let mut steps: Vec<StartupStepResult> = Vec::new();
+    let env_map = read_env_map(&PathBuf::from(&state.config_path).join(".env.v4.local"));
+    let gateway_timeout = phase_timeout_config(&env_map, "GATEWAY", 10_000, 30_000);
+    let worker_timeout = phase_timeout_config(&env_map, "WORKER", 15_000, 45_000);
+    let telegram_timeout = phase_timeout_config(&env_map, "TELEGRAM", 10_000, 30_000);
 
     let phase_started = now_iso();
     let checks = run_preflight_check_inner(&state);
-    let blockers = checks.iter().filter(|c| c.status == "blocker").count();
+    let blockers = checks
+        .iter()
+        .filter(|c| c.status == PreflightStatus::Blocker)
+        .count();
     if blockers > 0 {
         steps.push(step_result(
             "preflight",
@@ -2914,6 +5644,8 @@ fn start_openclaw_v2_inner(
             Some("auto_fix_preflight".to_string()),
             phase_started,
         ));
+        let app_err = AppError::preflight_blockers(format!("preflight blockers: {}", blockers));
+        export_otel_startup_span(&state, otel_start, false, &steps);
         best_effort_audit_operation(
             &state,
             AuditOperationPayload {
@@ -2921,11 +5653,13 @@ fn start_openclaw_v2_inner(
                 action: "openclaw_start_v2".to_string(),
                 status: "failed".to_string(),
                 summary: "start_openclaw_v2 failed at preflight".to_string(),
-                detail: Some(serde_json::json!({ "steps": steps, "blockers": blockers })),
+                detail: Some(
+                    serde_json::json!({ "steps": steps, "blockers": blockers, "code": app_err.code }),
+                ),
                 ..AuditOperationPayload::default()
             },
         );
-        return Err(format!("preflight blockers: {}", blockers));
+        return Err(app_err);
     }
     steps.push(step_result(
         "preflight",
@@ -2958,7 +5692,9 @@ fn start_openclaw_v2_inner(
     }
 
     let gateway_started = now_iso();
+    let gateway_phase_instant = std::time::Instant::now();
     let gateway_result = run_dispatcher_json(&state, &["gateway-start"]);
+    let gateway_elapsed_ms = gateway_phase_instant.elapsed().as_millis() as u64;
     if let Err(err) = gateway_result {
         steps.push(step_result(
             "start_gateway",
@@ -2967,6 +5703,7 @@ fn start_openclaw_v2_inner(
             Some("gateway-status".to_string()),
             gateway_started,
         ));
+        let app_err = AppError::gateway_start_failed("gateway start failed");
         best_effort_audit_operation(
             &state,
             AuditOperationPayload {
@@ -2974,26 +5711,69 @@ fn start_openclaw_v2_inner(
                 action: "openclaw_start_v2".to_string(),
                 status: "failed".to_string(),
                 summary: "start_openclaw_v2 failed at gateway".to_string(),
-                detail: Some(serde_json::json!({ "steps": steps })),
+                detail: Some(serde_json::json!({ "steps": steps, "code": app_err.code })),
                 ..AuditOperationPayload::default()
             },
         );
-        return Err("gateway start failed".to_string());
+        return Err(app_err);
     }
-    steps.push(step_result(
+    if gateway_elapsed_ms >= gateway_timeout.hard_ms {
+        let mut step = step_result(
+            "start_gateway",
+            "timeout",
+            format!(
+                "Gateway start exceeded the hard timeout ({}ms > {}ms)",
+                gateway_elapsed_ms, gateway_timeout.hard_ms
+            ),
+            Some("gateway-status".to_string()),
+            gateway_started,
+        );
+        step.duration_ms = Some(gateway_elapsed_ms as i64);
+        steps.push(step);
+        let app_err = AppError::startup_timeout("gateway start exceeded timeout");
+        best_effort_audit_operation(
+            &state,
+            AuditOperationPayload {
+                source: "tauri".to_string(),
+                action: "openclaw_start_v2".to_string(),
+                status: "failed".to_string(),
+                summary: "start_openclaw_v2 timed out at gateway".to_string(),
+                detail: Some(serde_json::json!({
+                    "steps": steps,
+                    "code": app_err.code,
+                    "duration_ms": gateway_elapsed_ms
+                })),
+                ..AuditOperationPayload::default()
+            },
+        );
+        return Err(app_err);
+    }
+    let (gateway_status, gateway_message) = if gateway_elapsed_ms >= gateway_timeout.soft_ms {
+        (
+            "warning",
+            format!("Gateway started slowly ({}ms)", gateway_elapsed_ms),
+        )
+    } else {
+        ("success", "Gateway started".to_string())
+    };
+    let mut gateway_step = step_result(
         "start_gateway",
-        "success",
-        "Gateway started".to_string(),
+        gateway_status,
+        gateway_message,
         None,
         gateway_started,
-    ));
+    );
+    gateway_step.duration_ms = Some(gateway_elapsed_ms as i64);
+    steps.push(gateway_step);
 
     let worker_started = now_iso();
+    let worker_phase_instant = std::time::Instant::now();
     let worker_result = if force_restart {
         run_start_script(&state, "--restart-worker")
     } else {
         run_start_script(&state, "--worker")
     };
+    let worker_elapsed_ms = worker_phase_instant.elapsed().as_millis() as u64;
     if let Err(err) = worker_result {
         steps.push(step_result(
             "start_worker",
@@ -3002,6 +5782,7 @@ fn start_openclaw_v2_inner(
             Some("restart_service worker".to_string()),
             worker_started,
         ));
+        let app_err = AppError::worker_start_failed("worker start failed");
         best_effort_audit_operation(
             &state,
             AuditOperationPayload {
@@ -3009,36 +5790,79 @@ fn start_openclaw_v2_inner(
                 action: "openclaw_start_v2".to_string(),
                 status: "failed".to_string(),
                 summary: "start_openclaw_v2 failed at worker".to_string(),
-                detail: Some(serde_json::json!({ "steps": steps })),
+                detail: Some(serde_json::json!({ "steps": steps, "code": app_err.code })),
                 ..AuditOperationPayload::default()
             },
         );
-        return Err("worker start failed".to_string());
+        return Err(app_err);
     }
-    steps.push(step_result(
-        "start_worker",
-        "success",
-        "Worker started".to_string(),
-        None,
-        worker_started,
-    ));
-
-    let tg_started = now_iso();
-    let telegram_health = match start_telegram_bot_v2_inner(
-        &state,
-        &StartTelegramPayload {
-            force_restart: Some(force_restart),
-        },
-    ) {
-        Ok(v) => v,
-        Err(err) => {
-            steps.push(step_result(
+    if worker_elapsed_ms >= worker_timeout.hard_ms {
+        let mut step = step_result(
+            "start_worker",
+            "timeout",
+            format!(
+                "Worker start exceeded the hard timeout ({}ms > {}ms)",
+                worker_elapsed_ms, worker_timeout.hard_ms
+            ),
+            Some("restart_service worker".to_string()),
+            worker_started,
+        );
+        step.duration_ms = Some(worker_elapsed_ms as i64);
+        steps.push(step);
+        let app_err = AppError::startup_timeout("worker start exceeded timeout");
+        best_effort_audit_operation(
+            &state,
+            AuditOperationPayload {
+                source: "tauri".to_string(),
+                action: "openclaw_start_v2".to_string(),
+                status: "failed".to_string(),
+                summary: "start_openclaw_v2 timed out at worker".to_string(),
+                detail: Some(serde_json::json!({
+                    "steps": steps,
+                    "code": app_err.code,
+                    "duration_ms": worker_elapsed_ms
+                })),
+                ..AuditOperationPayload::default()
+            },
+        );
+        return Err(app_err);
+    }
+    let (worker_status, worker_message) = if worker_elapsed_ms >= worker_timeout.soft_ms {
+        (
+            "warning",
+            format!("Worker started slowly ({}ms)", worker_elapsed_ms),
+        )
+    } else {
+        ("success", "Worker started".to_string())
+    };
+    let mut worker_step = step_result(
+        "start_worker",
+        worker_status,
+        worker_message,
+        None,
+        worker_started,
+    );
+    worker_step.duration_ms = Some(worker_elapsed_ms as i64);
+    steps.push(worker_step);
+
+    let tg_started = now_iso();
+    let telegram_phase_instant = std::time::Instant::now();
+    let telegram_health = match start_telegram_bot_v2_inner(
+        &state,
+        &StartTelegramPayload {
+            force_restart: Some(force_restart),
+        },
+    ) {
+        Ok(v) => v,
+        Err(err) => {
+            steps.push(step_result(
                 "start_telegram",
                 "failed",
                 format!("Failed to start telegram: {}", err),
                 Some("diagnose_telegram_bot".to_string()),
                 tg_started,
             ));
+            let app_err = AppError::telegram_start_failed("telegram start failed");
             best_effort_audit_operation(
                 &state,
                 AuditOperationPayload {
@@ -3046,20 +5870,62 @@ fn start_openclaw_v2_inner(
                     action: "openclaw_start_v2".to_string(),
                     status: "failed".to_string(),
                     summary: "start_openclaw_v2 failed at telegram".to_string(),
-                    detail: Some(serde_json::json!({ "steps": steps })),
+                    detail: Some(serde_json::json!({ "steps": steps, "code": app_err.code })),
                     ..AuditOperationPayload::default()
                 },
             );
-            return Err("telegram start failed".to_string());
+            return Err(app_err);
         }
     };
-    steps.push(step_result(
+    let telegram_elapsed_ms = telegram_phase_instant.elapsed().as_millis() as u64;
+    if telegram_elapsed_ms >= telegram_timeout.hard_ms {
+        let mut step = step_result(
+            "start_telegram",
+            "timeout",
+            format!(
+                "Telegram start exceeded the hard timeout ({}ms > {}ms)",
+                telegram_elapsed_ms, telegram_timeout.hard_ms
+            ),
+            Some("diagnose_telegram_bot".to_string()),
+            tg_started,
+        );
+        step.duration_ms = Some(telegram_elapsed_ms as i64);
+        steps.push(step);
+        let app_err = AppError::startup_timeout("telegram start exceeded timeout");
+        best_effort_audit_operation(
+            &state,
+            AuditOperationPayload {
+                source: "tauri".to_string(),
+                action: "openclaw_start_v2".to_string(),
+                status: "failed".to_string(),
+                summary: "start_openclaw_v2 timed out at telegram".to_string(),
+                detail: Some(serde_json::json!({
+                    "steps": steps,
+                    "code": app_err.code,
+                    "duration_ms": telegram_elapsed_ms
+                })),
+                ..AuditOperationPayload::default()
+            },
+        );
+        return Err(app_err);
+    }
+    let (telegram_status, telegram_message) = if telegram_elapsed_ms >= telegram_timeout.soft_ms {
+        (
+            "warning",
+            format!("Telegram bot started slowly ({}ms)", telegram_elapsed_ms),
+        )
+    } else {
+        ("success", "Telegram bot started".to_string())
+    };
+    let mut telegram_step = step_result(
         "start_telegram",
-        "success",
-        "Telegram bot started".to_string(),
+        telegram_status,
+        telegram_message,
         None,
         tg_started,
-    ));
+    );
+    telegram_step.duration_ms = Some(telegram_elapsed_ms as i64);
+    steps.push(telegram_step);
 
     let verify_started = now_iso();
     let services = get_service_status_inner(&state)?;
@@ -3088,6 +5954,7 @@ fn start_openclaw_v2_inner(
             None,
             now_iso(),
         ));
+        auto_drain_retry_backlog(&state);
     } else {
         steps.push(step_result(
             "verify",
@@ -3099,6 +5966,8 @@ fn start_openclaw_v2_inner(
             Some("get_startup_snapshot".to_string()),
             verify_started,
         ));
+        let app_err = AppError::verify_failed("startup verification failed");
+        export_otel_startup_span(&state, otel_start, false, &steps);
         best_effort_audit_operation(
             &state,
             AuditOperationPayload {
@@ -3106,13 +5975,14 @@ fn start_openclaw_v2_inner(
                 action: "openclaw_start_v2".to_string(),
                 status: "failed".to_string(),
                 summary: "start_openclaw_v2 failed at verify".to_string(),
-                detail: Some(serde_json::json!({ "steps": steps })),
+                detail: Some(serde_json::json!({ "steps": steps, "code": app_err.code })),
                 ..AuditOperationPayload::default()
             },
         );
-        return Err("startup verification failed".to_string());
+        return Err(app_err);
     }
 
+    export_otel_startup_span(&state, otel_start, true, &steps);
     best_effort_audit_operation(
         &state,
         AuditOperationPayload {
@@ -3127,6 +5997,39 @@ fn start_openclaw_v2_inner(
     Ok(steps)
 }
 
+/// Span covering one `start_openclaw_v2_inner` run, with a child event per
+/// `StartupStepResult` (preflight / login_check / gateway / worker /
+/// telegram / verify / done).
+fn export_otel_startup_span(
+    state: &AppState,
+    start_unix_nano: u128,
+    status_ok: bool,
+    steps: &[StartupStepResult],
+) {
+    if otel_otlp_endpoint(state).is_none() {
+        return;
+    }
+    let now = otel_now_unix_nano();
+    export_otel_span(
+        state,
+        OtelSpan {
+            name: "openclaw.start_v2".to_string(),
+            start_unix_nano,
+            end_unix_nano: now,
+            status_ok,
+            attributes: serde_json::json!({ "steps.count": steps.len() as i64 }),
+            events: steps
+                .iter()
+                .map(|s| OtelSpanEvent {
+                    name: format!("startup.step.{}", s.phase),
+                    time_unix_nano: now,
+                    attributes: serde_json::to_value(s).unwrap_or(serde_json::Value::Null),
+                })
+                .collect(),
+        },
+    );
+}
+
 #[tauri::command]
 async fn start_openclaw(state: State<'_, AppState>) -> Result<Vec<PreflightCheck>, String> {
     let _ = start_openclaw_v2_inner(
@@ -3175,8 +6078,28 @@ fn run_preflight_check(state: State<'_, AppState>) -> Vec<PreflightCheck> {
 #[tauri::command]
 fn get_model_availability_report(
     state: State<'_, AppState>,
+    force_refresh: Option<bool>,
 ) -> Result<ModelAvailabilityReport, String> {
-    compute_model_availability_report_inner(&state)
+    get_cached_availability_report(&state, force_refresh.unwrap_or(false))
+}
+
+/// Given the vision backend ids already tried (and failed) this Format QA
+/// run, returns the next backend in the resolved failover chain, or `None`
+/// once every configured backend has been exhausted. Lets the caller
+/// advance transparently instead of aborting on the first failure.
+#[tauri::command]
+fn resolve_next_vision_backend(
+    state: State<'_, AppState>,
+    failed: Vec<String>,
+) -> Result<Option<String>, String> {
+    let report = get_cached_availability_report(&state, false)?;
+    let failed: std::collections::HashSet<String> =
+        failed.into_iter().map(|id| id.to_lowercase()).collect();
+    Ok(report
+        .vision
+        .resolved_chain
+        .into_iter()
+        .find(|candidate| !failed.contains(&candidate.to_lowercase())))
 }
 
 // ============================================================================
@@ -3184,12 +6107,12 @@ fn get_model_availability_report(
 // ============================================================================
 
 #[tauri::command]
-fn get_config(state: State<'_, AppState>) -> Result<AppConfig, String> {
+fn get_config(state: State<'_, AppState>) -> Result<AppConfig, TranslationError> {
     get_config_inner(&state)
 }
 
 #[tauri::command]
-fn save_config(config: AppConfig, state: State<'_, AppState>) -> Result<(), String> {
+fn save_config(config: AppConfig, state: State<'_, AppState>) -> Result<(), AppError> {
     let env_path = format!("{}/.env.v4.local", state.config_path);
 
     // Read existing content to preserve other values
@@ -3213,13 +6136,14 @@ fn save_config(config: AppConfig, state: State<'_, AppState>) -> Result<(), Stri
     update_or_add_env_line(&mut lines, "OPENCLAW_RAG_BACKEND", &config.rag_backend);
 
     let content = lines.join("\n");
-    fs::write(&env_path, content).map_err(|e| format!("Failed to write config: {}", e))?;
+    fs::write(&env_path, content)
+        .map_err(|e| AppError::config_write_failed(format!("Failed to write config: {}", e)))?;
 
     Ok(())
 }
 
 #[tauri::command]
-fn get_env_settings(state: State<'_, AppState>) -> Result<Vec<EnvVarItem>, String> {
+fn get_env_settings(state: State<'_, AppState>) -> Result<Vec<EnvVarItem>, TranslationError> {
     let env_path = PathBuf::from(&state.config_path).join(".env.v4.local");
     let content = fs::read_to_string(&env_path).unwrap_or_default();
 
@@ -3238,7 +6162,10 @@ fn get_env_settings(state: State<'_, AppState>) -> Result<Vec<EnvVarItem>, Strin
 }
 
 #[tauri::command]
-fn save_env_settings(updates: Vec<EnvVarUpdate>, state: State<'_, AppState>) -> Result<(), String> {
+fn save_env_settings(
+    updates: Vec<EnvVarUpdate>,
+    state: State<'_, AppState>,
+) -> Result<(), TranslationError> {
     let env_path = PathBuf::from(&state.config_path).join(".env.v4.local");
     let existing = fs::read_to_string(&env_path).unwrap_or_default();
     let mut lines: Vec<String> = existing.lines().map(|s| s.to_string()).collect();
@@ -3253,7 +6180,7 @@ fn save_env_settings(updates: Vec<EnvVarUpdate>, state: State<'_, AppState>) ->
     }
 
     let content = lines.join("\n");
-    fs::write(&env_path, content).map_err(|e| format!("Failed to write env settings: {}", e))?;
+    fs::write(&env_path, content)?;
     Ok(())
 }
 
@@ -3267,10 +6194,12 @@ fn get_jobs(
     limit: Option<u32>,
     state: State<'_, AppState>,
 ) -> Result<Vec<JobInfo>, String> {
-    use rusqlite::Connection;
-
-    let conn =
-        Connection::open(&state.db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
+    ensure_job_retry_columns(&conn).map_err(|e| format!("Failed to migrate jobs table: {}", e))?;
+    ensure_job_attempt_columns(&conn).map_err(|e| format!("Failed to migrate jobs table: {}", e))?;
 
     let limit = limit.unwrap_or(50);
 
@@ -3279,7 +6208,7 @@ fn get_jobs(
     match status {
         Some(s) => {
             let mut stmt = conn.prepare(
-                "SELECT job_id, status, task_type, sender, created_at, updated_at FROM jobs WHERE status = ?1 ORDER BY created_at DESC LIMIT ?2"
+                "SELECT job_id, status, task_type, sender, created_at, updated_at, error_count, last_try, next_try, attempt_count, next_retry_at FROM jobs WHERE status = ?1 ORDER BY created_at DESC LIMIT ?2"
             ).map_err(|e| format!("Failed to prepare query: {}", e))?;
 
             let rows = stmt
@@ -3291,6 +6220,11 @@ fn get_jobs(
                         sender: row.get(3)?,
                         created_at: row.get(4)?,
                         updated_at: row.get(5)?,
+                        error_count: row.get(6)?,
+                        last_try: row.get(7)?,
+                        next_try: row.get(8)?,
+                        attempt_count: row.get(9)?,
+                        next_retry_at: row.get(10)?,
                     })
                 })
                 .map_err(|e| format!("Failed to query jobs: {}", e))?;
@@ -3301,7 +6235,7 @@ fn get_jobs(
         }
         None => {
             let mut stmt = conn.prepare(
-                "SELECT job_id, status, task_type, sender, created_at, updated_at FROM jobs ORDER BY created_at DESC LIMIT ?1"
+                "SELECT job_id, status, task_type, sender, created_at, updated_at, error_count, last_try, next_try, attempt_count, next_retry_at FROM jobs ORDER BY created_at DESC LIMIT ?1"
             ).map_err(|e| format!("Failed to prepare query: {}", e))?;
 
             let rows = stmt
@@ -3313,6 +6247,11 @@ fn get_jobs(
                         sender: row.get(3)?,
                         created_at: row.get(4)?,
                         updated_at: row.get(5)?,
+                        error_count: row.get(6)?,
+                        last_try: row.get(7)?,
+                        next_try: row.get(8)?,
+                        attempt_count: row.get(9)?,
+                        next_retry_at: row.get(10)?,
                     })
                 })
                 .map_err(|e| format!("Failed to query jobs: {}", e))?;
@@ -3331,10 +6270,10 @@ fn get_job_milestones(
     job_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<Milestone>, String> {
-    use rusqlite::Connection;
-
-    let conn =
-        Connection::open(&state.db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
 
     let mut stmt = conn.prepare(
         "SELECT job_id, milestone, created_at, payload_json FROM events WHERE job_id = ?1 ORDER BY created_at ASC"
@@ -3356,205 +6295,850 @@ fn get_job_milestones(
     Ok(milestones)
 }
 
-fn load_alert_state_snapshot(path: &str) -> AlertStateSnapshot {
-    let content = match fs::read_to_string(path) {
-        Ok(content) => content,
-        Err(_) => return AlertStateSnapshot::default(),
-    };
+// ============================================================================
+// Job Retry Subsystem
+// ============================================================================
 
-    if let Ok(snapshot) = serde_json::from_str::<AlertStateSnapshot>(&content) {
-        return snapshot;
+/// Backoff delay at `error_count` 0; doubles per attempt (modeled on pict-rs'
+/// job retry backoff). Retries pass the post-increment count, so a job's
+/// first retry waits twice this.
+const RETRY_BASE_DELAY_MS: i64 = 30_000;
+/// Upper bound on the backoff delay so a job with many failures doesn't end
+/// up scheduled days out.
+const RETRY_MAX_DELAY_MS: i64 = 30 * 60_000;
+/// Attempts allowed before a job is left with no scheduled retry (terminal).
+const RETRY_MAX_ATTEMPTS: i64 = 5;
+
+/// Adds the `error_count`/`last_try`/`next_try` columns to the `jobs` table
+/// if they aren't already there. The dispatcher-owned schema predates the
+/// retry subsystem, so this runs as a lazy, idempotent migration rather than
+/// assuming a fixed column set.
+fn ensure_job_retry_columns(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(jobs)")?;
+    let existing: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !existing.iter().any(|c| c == "error_count") {
+        conn.execute(
+            "ALTER TABLE jobs ADD COLUMN error_count INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
     }
+    if !existing.iter().any(|c| c == "last_try") {
+        conn.execute("ALTER TABLE jobs ADD COLUMN last_try TEXT", [])?;
+    }
+    if !existing.iter().any(|c| c == "next_try") {
+        conn.execute("ALTER TABLE jobs ADD COLUMN next_try TEXT", [])?;
+    }
+    Ok(())
+}
 
-    if let Ok(legacy_ack_ids) = serde_json::from_str::<Vec<String>>(&content) {
-        return AlertStateSnapshot {
-            acknowledged_ids: legacy_ack_ids.into_iter().collect(),
-            ..AlertStateSnapshot::default()
-        };
+/// `next_try` delay in milliseconds for the given (post-increment) attempt
+/// count: `base_delay * 2^error_count`, capped at `RETRY_MAX_DELAY_MS`.
+fn compute_retry_delay_ms(error_count: i64) -> i64 {
+    let shift = error_count.clamp(0, 32) as u32;
+    RETRY_BASE_DELAY_MS
+        .saturating_mul(1_i64 << shift)
+        .min(RETRY_MAX_DELAY_MS)
+}
+
+/// Base backoff delay (seconds) used by `requeue_failed_jobs`, the bulk
+/// failed-queue recovery mechanism. Distinct from `RETRY_BASE_DELAY_MS`,
+/// which backs the single-job `requeue_job` command.
+const FAILED_REQUEUE_BASE_DELAY_SECS: i64 = 30;
+/// Upper bound on the `requeue_failed_jobs` backoff delay.
+const FAILED_REQUEUE_MAX_DELAY_SECS: i64 = 30 * 60;
+/// Attempts allowed before `requeue_failed_jobs` stops requeuing a job and
+/// leaves it permanently failed.
+const FAILED_REQUEUE_MAX_ATTEMPTS: i64 = 5;
+
+/// Adds the `attempt_count`/`next_retry_at` columns to the `jobs` table if
+/// they aren't already there. These back `requeue_failed_jobs`, which is a
+/// separate bulk-recovery mechanism from the `error_count`/`next_try`
+/// columns `requeue_job` uses.
+fn ensure_job_attempt_columns(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(jobs)")?;
+    let existing: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !existing.iter().any(|c| c == "attempt_count") {
+        conn.execute(
+            "ALTER TABLE jobs ADD COLUMN attempt_count INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
     }
+    if !existing.iter().any(|c| c == "next_retry_at") {
+        conn.execute("ALTER TABLE jobs ADD COLUMN next_retry_at TEXT", [])?;
+    }
+    Ok(())
+}
 
-    AlertStateSnapshot::default()
+/// `next_retry_at` delay in seconds for the given (post-increment) attempt
+/// count: `base_delay * 2^(attempt-1)`, capped at `FAILED_REQUEUE_MAX_DELAY_SECS`.
+fn compute_failed_requeue_delay_secs(attempt_count: i64) -> i64 {
+    let shift = (attempt_count - 1).clamp(0, 32) as u32;
+    FAILED_REQUEUE_BASE_DELAY_SECS
+        .saturating_mul(1_i64 << shift)
+        .min(FAILED_REQUEUE_MAX_DELAY_SECS)
 }
 
-fn persist_alert_state_snapshot(path: &str, snapshot: &AlertStateSnapshot) -> Result<(), String> {
-    let path_buf = PathBuf::from(path);
-    if let Some(parent) = path_buf.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to prepare alert state dir: {}", e))?;
+fn requeue_job_inner(state: &AppState, job_id: &str) -> Result<JobInfo, AppError> {
+    let conn = state.db_pool.get().map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to get pooled connection: {}", e))
+    })?;
+    ensure_job_retry_columns(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+    ensure_job_attempt_columns(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+
+    let (status, error_count): (String, i64) = conn
+        .query_row(
+            "SELECT status, error_count FROM jobs WHERE job_id = ?1",
+            rusqlite::params![job_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|_| AppError::invalid_job(format!("Job {} not found", job_id)))?;
+
+    // Never retry a job already in a terminal state.
+    let status_lower = status.to_lowercase();
+    if status_lower == "verified" || status_lower == "cancelled" {
+        return Err(AppError::invalid_job(format!(
+            "Job {} is already {}; nothing to retry",
+            job_id, status_lower
+        )));
+    }
+
+    let now = now_iso();
+    if error_count >= RETRY_MAX_ATTEMPTS {
+        // Exhausted: clear any pending schedule so it no longer surfaces in
+        // get_retryable_jobs, and record that we gave up.
+        conn.execute(
+            "UPDATE jobs SET last_try = ?1, next_try = NULL, updated_at = ?1 WHERE job_id = ?2",
+            rusqlite::params![now, job_id],
+        )
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to update job: {}", e)))?;
+        insert_job_milestone(&conn, job_id, "retry_exhausted", &now, None).map_err(|e| {
+            AppError::new("job-db-error", format!("Failed to record milestone: {}", e))
+        })?;
+        return Err(AppError::invalid_job(format!(
+            "Job {} has exhausted its {} retry attempts",
+            job_id, RETRY_MAX_ATTEMPTS
+        )));
+    }
+
+    let new_error_count = error_count + 1;
+    let delay_ms = compute_retry_delay_ms(new_error_count);
+    let next_try = (Utc::now() + Duration::milliseconds(delay_ms)).to_rfc3339();
+
+    // Persist error_count/last_try/next_try together with the status update
+    // in a single statement so a crash mid-retry can't leave the schedule
+    // and the attempt count out of sync.
+    conn.execute(
+        "UPDATE jobs SET status = 'pending', error_count = ?1, last_try = ?2, next_try = ?3, updated_at = ?2 WHERE job_id = ?4",
+        rusqlite::params![new_error_count, now, next_try, job_id],
+    )
+    .map_err(|e| AppError::new("job-db-error", format!("Failed to update job: {}", e)))?;
+
+    insert_job_milestone(
+        &conn,
+        job_id,
+        "retry_scheduled",
+        &now,
+        Some(
+            serde_json::json!({ "error_count": new_error_count, "next_try": next_try })
+                .to_string(),
+        ),
+    )
+    .map_err(|e| AppError::new("job-db-error", format!("Failed to record milestone: {}", e)))?;
+
+    load_job_info(&conn, job_id)
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to reload job: {}", e)))
+}
+
+fn insert_job_milestone(
+    conn: &rusqlite::Connection,
+    job_id: &str,
+    milestone: &str,
+    created_at: &str,
+    payload_json: Option<String>,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO events (job_id, milestone, created_at, payload_json) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![job_id, milestone, created_at, payload_json],
+    )?;
+    Ok(())
+}
+
+#[tauri::command]
+fn requeue_job(job_id: String, state: State<'_, AppState>) -> Result<JobInfo, AppError> {
+    requeue_job_inner(&state, &job_id)
+}
+
+/// Resets every currently-failed job back to `pending` so the worker picks
+/// it up again, unless it has already exhausted `FAILED_REQUEUE_MAX_ATTEMPTS`
+/// — those are left as `failed` (a distinct `jobs_retry_exhausted` alert
+/// surfaces them instead). This is a bulk recovery mechanism, separate from
+/// the single-job `requeue_job` command above.
+fn requeue_failed_jobs_inner(state: &AppState) -> Result<Vec<JobInfo>, AppError> {
+    let conn = state.db_pool.get().map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to get pooled connection: {}", e))
+    })?;
+    ensure_job_retry_columns(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+    ensure_job_attempt_columns(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+
+    let mut stmt = conn
+        .prepare("SELECT job_id, attempt_count FROM jobs WHERE LOWER(status) = 'failed'")
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to prepare query: {}", e)))?;
+    let candidates: Vec<(String, i64)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to query jobs: {}", e)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to collect jobs: {}", e)))?;
+    drop(stmt);
+
+    let now = now_iso();
+    let mut requeued = Vec::new();
+    let mut exhausted_count = 0u64;
+
+    for (job_id, attempt_count) in candidates {
+        if attempt_count >= FAILED_REQUEUE_MAX_ATTEMPTS {
+            insert_job_milestone(&conn, &job_id, "requeue_exhausted", &now, None).map_err(|e| {
+                AppError::new("job-db-error", format!("Failed to record milestone: {}", e))
+            })?;
+            exhausted_count += 1;
+            continue;
+        }
+
+        let new_attempt_count = attempt_count + 1;
+        let delay_secs = compute_failed_requeue_delay_secs(new_attempt_count);
+        let next_retry_at = (Utc::now() + Duration::seconds(delay_secs)).to_rfc3339();
+
+        conn.execute(
+            "UPDATE jobs SET status = 'pending', attempt_count = ?1, next_retry_at = ?2, updated_at = ?3 WHERE job_id = ?4",
+            rusqlite::params![new_attempt_count, next_retry_at, now, job_id],
+        )
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to update job: {}", e)))?;
+
+        insert_job_milestone(
+            &conn,
+            &job_id,
+            "requeued",
+            &now,
+            Some(
+                serde_json::json!({ "attempt_count": new_attempt_count, "next_retry_at": next_retry_at })
+                    .to_string(),
+            ),
+        )
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to record milestone: {}", e)))?;
+
+        requeued.push(
+            load_job_info(&conn, &job_id)
+                .map_err(|e| AppError::new("job-db-error", format!("Failed to reload job: {}", e)))?,
+        );
     }
 
-    let payload = serde_json::to_string_pretty(snapshot)
-        .map_err(|e| format!("Failed to serialize alert state: {}", e))?;
-    fs::write(path, payload).map_err(|e| format!("Failed to persist alert state: {}", e))?;
+    best_effort_audit_operation(
+        state,
+        AuditOperationPayload {
+            source: "tauri".to_string(),
+            action: "requeue_failed_jobs".to_string(),
+            status: "success".to_string(),
+            summary: format!(
+                "Requeued {} failed job(s), {} already exhausted",
+                requeued.len(),
+                exhausted_count
+            ),
+            detail: Some(serde_json::json!({ "requeued": requeued.len(), "exhausted": exhausted_count })),
+            ..AuditOperationPayload::default()
+        },
+    );
+
+    Ok(requeued)
+}
+
+#[tauri::command]
+fn requeue_failed_jobs(state: State<'_, AppState>) -> Result<Vec<JobInfo>, AppError> {
+    requeue_failed_jobs_inner(&state)
+}
+
+// ============================================================================
+// Job Mutation Commands (cancel / reprioritize / purge)
+// ============================================================================
+
+/// Adds the `priority` column to the `jobs` table if it isn't already
+/// there, mirroring `ensure_job_retry_columns`'s lazy-migration approach.
+/// Higher values sort earlier in the worker's pick order.
+fn ensure_job_priority_column(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(jobs)")?;
+    let existing: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !existing.iter().any(|c| c == "priority") {
+        conn.execute(
+            "ALTER TABLE jobs ADD COLUMN priority INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
     Ok(())
 }
 
-fn default_warning_to_critical_minutes() -> u32 {
-    30
+/// Creates `kb_files` if a fresh DB doesn't have it yet (the table is
+/// normally seeded by the Python `kb-sync` step, but a from-scratch
+/// `state.sqlite` shouldn't make `get_kb_stats`/`list_kb_files` error out
+/// before the first sync has run).
+fn ensure_kb_files_table(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS kb_files (
+            path TEXT PRIMARY KEY,
+            parser TEXT,
+            source_group TEXT,
+            chunk_count INTEGER NOT NULL DEFAULT 0,
+            indexed_at TEXT,
+            size_bytes INTEGER NOT NULL DEFAULT 0
+        )",
+    )
+}
+
+/// Additive `kb_files` columns: `content_hash` lets a future sync skip
+/// re-chunking unchanged files, `language` lets the KB browser filter/group
+/// by source language.
+fn ensure_kb_files_columns(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    ensure_kb_files_table(conn)?;
+    let mut stmt = conn.prepare("PRAGMA table_info(kb_files)")?;
+    let existing: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !existing.iter().any(|c| c == "content_hash") {
+        conn.execute("ALTER TABLE kb_files ADD COLUMN content_hash TEXT", [])?;
+    }
+    if !existing.iter().any(|c| c == "language") {
+        conn.execute("ALTER TABLE kb_files ADD COLUMN language TEXT", [])?;
+    }
+    Ok(())
 }
 
-fn default_alert_policy_config() -> AlertPolicyConfig {
-    AlertPolicyConfig {
-        warning_to_critical_minutes: default_warning_to_critical_minutes(),
-        runbooks: vec![
-            AlertRunbookRuleConfig {
-                source: Some("service".to_string()),
-                severity: None,
-                headline: "Service health issue".to_string(),
-                steps: vec![
-                    "Open Service Control and confirm which process is stopped or degraded."
-                        .to_string(),
-                    "Restart the affected service, then verify status returns to running."
-                        .to_string(),
-                    "Open Technical Logs and confirm new ERROR lines stop increasing.".to_string(),
-                    "Return to Overview and confirm open alerts and backlog begin to drop."
-                        .to_string(),
-                ],
-                actions: vec![
-                    AlertRunbookAction {
-                        label: "Open Service Control".to_string(),
-                        tab: "services".to_string(),
-                    },
-                    AlertRunbookAction {
-                        label: "Open Technical Logs".to_string(),
-                        tab: "logs".to_string(),
-                    },
-                    AlertRunbookAction {
-                        label: "Open Overview".to_string(),
-                        tab: "dashboard".to_string(),
-                    },
-                ],
-            },
-            AlertRunbookRuleConfig {
-                source: Some("jobs".to_string()),
-                severity: None,
-                headline: "Job failure cluster".to_string(),
-                steps: vec![
-                    "Open Task Center and inspect the most recent failed jobs first.".to_string(),
-                    "Check whether failures share the same source file, sender, or task type."
-                        .to_string(),
-                    "If failures repeat, verify services and logs before rerunning jobs."
-                        .to_string(),
-                    "Monitor recovery in Overview success rate and open alerts.".to_string(),
-                ],
-                actions: vec![
-                    AlertRunbookAction {
-                        label: "Open Task Center".to_string(),
-                        tab: "jobs".to_string(),
-                    },
-                    AlertRunbookAction {
-                        label: "Open Technical Logs".to_string(),
-                        tab: "logs".to_string(),
-                    },
-                    AlertRunbookAction {
-                        label: "Open Overview".to_string(),
-                        tab: "dashboard".to_string(),
-                    },
-                ],
-            },
-            AlertRunbookRuleConfig {
-                source: Some("verify".to_string()),
-                severity: None,
-                headline: "Review queue accumulation".to_string(),
-                steps: vec![
-                    "Open Review Desk and prioritize the oldest review_ready jobs first."
-                        .to_string(),
-                    "Process urgent customer-facing files before batch jobs.".to_string(),
-                    "Confirm reviewed jobs leave the queue and no new blockers appear.".to_string(),
-                ],
-                actions: vec![
-                    AlertRunbookAction {
-                        label: "Open Review Desk".to_string(),
-                        tab: "verify".to_string(),
-                    },
-                    AlertRunbookAction {
-                        label: "Open Task Center".to_string(),
-                        tab: "jobs".to_string(),
-                    },
-                ],
-            },
-            AlertRunbookRuleConfig {
-                source: Some("queue".to_string()),
-                severity: None,
-                headline: "Pending queue pressure".to_string(),
-                steps: vec![
-                    "Open Overview Queue Board and identify where jobs accumulate.".to_string(),
-                    "If pending is high, verify worker health and processing throughput."
-                        .to_string(),
-                    "If running is high for too long, inspect logs for retries or API errors."
-                        .to_string(),
-                ],
-                actions: vec![
-                    AlertRunbookAction {
-                        label: "Open Overview".to_string(),
-                        tab: "dashboard".to_string(),
-                    },
-                    AlertRunbookAction {
-                        label: "Open Service Control".to_string(),
+fn load_job_info(conn: &rusqlite::Connection, job_id: &str) -> rusqlite::Result<JobInfo> {
+    conn.query_row(
+        "SELECT job_id, status, task_type, sender, created_at, updated_at, error_count, last_try, next_try, attempt_count, next_retry_at FROM jobs WHERE job_id = ?1",
+        rusqlite::params![job_id],
+        |row| {
+            Ok(JobInfo {
+                job_id: row.get(0)?,
+                status: row.get(1)?,
+                task_type: row.get(2)?,
+                sender: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+                error_count: row.get(6)?,
+                last_try: row.get(7)?,
+                next_try: row.get(8)?,
+                attempt_count: row.get(9)?,
+                next_retry_at: row.get(10)?,
+            })
+        },
+    )
+}
+
+fn cancel_job_inner(state: &AppState, job_id: &str, force: bool) -> Result<JobInfo, AppError> {
+    let conn = state.db_pool.get().map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to get pooled connection: {}", e))
+    })?;
+    ensure_job_retry_columns(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+    ensure_job_attempt_columns(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+
+    let status: String = conn
+        .query_row(
+            "SELECT status FROM jobs WHERE job_id = ?1",
+            rusqlite::params![job_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::invalid_job(format!("Job {} not found", job_id)))?;
+    let status_lower = status.to_lowercase();
+
+    if status_lower == "verified" || status_lower == "cancelled" {
+        return Err(AppError::invalid_job(format!(
+            "Job {} is already {}; nothing to cancel",
+            job_id, status_lower
+        )));
+    }
+    if status_lower == "running" && !force {
+        return Err(AppError::invalid_job(format!(
+            "Job {} is currently running; pass force to cancel anyway",
+            job_id
+        )));
+    }
+
+    let now = now_iso();
+    conn.execute(
+        "UPDATE jobs SET status = 'cancelled', next_try = NULL, updated_at = ?1 WHERE job_id = ?2",
+        rusqlite::params![now, job_id],
+    )
+    .map_err(|e| AppError::new("job-db-error", format!("Failed to update job: {}", e)))?;
+    insert_job_milestone(
+        &conn,
+        job_id,
+        "cancelled",
+        &now,
+        Some(serde_json::json!({ "previous_status": status, "force": force }).to_string()),
+    )
+    .map_err(|e| AppError::new("job-db-error", format!("Failed to record milestone: {}", e)))?;
+
+    let job = load_job_info(&conn, job_id)
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to reload job: {}", e)))?;
+
+    best_effort_audit_operation(
+        state,
+        AuditOperationPayload {
+            source: "tauri".to_string(),
+            action: "cancel_job".to_string(),
+            job_id: Some(job_id.to_string()),
+            status: "success".to_string(),
+            summary: format!("Cancelled job {} (was {})", job_id, status),
+            detail: Some(serde_json::json!({ "previous_status": status, "force": force })),
+            ..AuditOperationPayload::default()
+        },
+    );
+
+    Ok(job)
+}
+
+#[tauri::command]
+fn cancel_job(
+    job_id: String,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<JobInfo, AppError> {
+    cancel_job_inner(&state, &job_id, force.unwrap_or(false))
+}
+
+fn set_job_priority_inner(
+    state: &AppState,
+    job_id: &str,
+    priority: i64,
+    force: bool,
+) -> Result<JobInfo, AppError> {
+    let conn = state.db_pool.get().map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to get pooled connection: {}", e))
+    })?;
+    ensure_job_retry_columns(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+    ensure_job_priority_column(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+    ensure_job_attempt_columns(&conn).map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to migrate jobs table: {}", e))
+    })?;
+
+    let status: String = conn
+        .query_row(
+            "SELECT status FROM jobs WHERE job_id = ?1",
+            rusqlite::params![job_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::invalid_job(format!("Job {} not found", job_id)))?;
+    if status.to_lowercase() == "running" && !force {
+        return Err(AppError::invalid_job(format!(
+            "Job {} is currently running; pass force to reprioritize anyway",
+            job_id
+        )));
+    }
+
+    let now = now_iso();
+    conn.execute(
+        "UPDATE jobs SET priority = ?1, updated_at = ?2 WHERE job_id = ?3",
+        rusqlite::params![priority, now, job_id],
+    )
+    .map_err(|e| AppError::new("job-db-error", format!("Failed to update job: {}", e)))?;
+    insert_job_milestone(
+        &conn,
+        job_id,
+        "priority_changed",
+        &now,
+        Some(serde_json::json!({ "priority": priority }).to_string()),
+    )
+    .map_err(|e| AppError::new("job-db-error", format!("Failed to record milestone: {}", e)))?;
+
+    let job = load_job_info(&conn, job_id)
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to reload job: {}", e)))?;
+
+    best_effort_audit_operation(
+        state,
+        AuditOperationPayload {
+            source: "tauri".to_string(),
+            action: "set_job_priority".to_string(),
+            job_id: Some(job_id.to_string()),
+            status: "success".to_string(),
+            summary: format!("Set priority {} on job {}", priority, job_id),
+            detail: Some(serde_json::json!({ "priority": priority, "force": force })),
+            ..AuditOperationPayload::default()
+        },
+    );
+
+    Ok(job)
+}
+
+#[tauri::command]
+fn set_job_priority(
+    job_id: String,
+    priority: i64,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<JobInfo, AppError> {
+    set_job_priority_inner(&state, &job_id, priority, force.unwrap_or(false))
+}
+
+/// Deletes rows matching `status` whose `created_at` is older than
+/// `older_than_hours`. `status` is not checked against the terminal states:
+/// any status other than `running` is accepted, so e.g. `pending` rows are
+/// deleted too. Refuses to purge `running` jobs outright -- unlike
+/// `cancel_job`/`set_job_priority` there is no `force` escape hatch here,
+/// since purge is destructive and irreversible.
+fn purge_jobs_inner(state: &AppState, status: &str, older_than_hours: i64) -> Result<u64, AppError> {
+    if status.eq_ignore_ascii_case("running") {
+        return Err(AppError::invalid_job(
+            "Refusing to purge jobs with status 'running'",
+        ));
+    }
+
+    let conn = state.db_pool.get().map_err(|e| {
+        AppError::new("job-db-error", format!("Failed to get pooled connection: {}", e))
+    })?;
+    let cutoff = (Utc::now() - Duration::hours(older_than_hours)).to_rfc3339();
+
+    let mut stmt = conn
+        .prepare("SELECT job_id FROM jobs WHERE status = ?1 AND created_at <= ?2")
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to prepare query: {}", e)))?;
+    let job_ids: Vec<String> = stmt
+        .query_map(rusqlite::params![status, cutoff], |row| row.get(0))
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to query jobs: {}", e)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to collect jobs: {}", e)))?;
+    drop(stmt);
+
+    let now = now_iso();
+    for job_id in &job_ids {
+        let _ = insert_job_milestone(
+            &conn,
+            job_id,
+            "purged",
+            &now,
+            Some(serde_json::json!({ "status": status, "older_than_hours": older_than_hours }).to_string()),
+        );
+    }
+
+    let purged = conn
+        .execute(
+            "DELETE FROM jobs WHERE status = ?1 AND created_at <= ?2",
+            rusqlite::params![status, cutoff],
+        )
+        .map_err(|e| AppError::new("job-db-error", format!("Failed to delete jobs: {}", e)))?
+        as u64;
+
+    best_effort_audit_operation(
+        state,
+        AuditOperationPayload {
+            source: "tauri".to_string(),
+            action: "purge_jobs".to_string(),
+            status: "success".to_string(),
+            summary: format!(
+                "Purged {} job(s) with status '{}' older than {}h",
+                purged, status, older_than_hours
+            ),
+            detail: Some(serde_json::json!({ "status": status, "older_than_hours": older_than_hours, "purged": purged, "job_ids": job_ids })),
+            ..AuditOperationPayload::default()
+        },
+    );
+
+    Ok(purged)
+}
+
+#[tauri::command]
+fn purge_jobs(
+    status: String,
+    older_than_hours: i64,
+    state: State<'_, AppState>,
+) -> Result<u64, AppError> {
+    purge_jobs_inner(&state, &status, older_than_hours)
+}
+
+/// Jobs due for a retry per `next_try`, excluding the terminal statuses
+/// (`verified`, `cancelled`) -- a job can reach either of those with a
+/// stale `next_try` still set (e.g. it's verified out-of-band while a
+/// retry was already scheduled), and surfacing it here would make
+/// `auto_drain_retry_backlog` fire a spurious retry against an
+/// already-finished job.
+fn get_retryable_jobs_inner(state: &AppState) -> Result<Vec<JobInfo>, String> {
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
+    ensure_job_retry_columns(&conn).map_err(|e| format!("Failed to migrate jobs table: {}", e))?;
+    ensure_job_attempt_columns(&conn).map_err(|e| format!("Failed to migrate jobs table: {}", e))?;
+
+    let now = now_iso();
+    let mut stmt = conn
+        .prepare(
+            "SELECT job_id, status, task_type, sender, created_at, updated_at, error_count, last_try, next_try, attempt_count, next_retry_at
+             FROM jobs
+             WHERE next_try IS NOT NULL AND next_try <= ?1
+             AND LOWER(status) NOT IN ('verified', 'cancelled')
+             ORDER BY next_try ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![now], |row| {
+            Ok(JobInfo {
+                job_id: row.get(0)?,
+                status: row.get(1)?,
+                task_type: row.get(2)?,
+                sender: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+                error_count: row.get(6)?,
+                last_try: row.get(7)?,
+                next_try: row.get(8)?,
+                attempt_count: row.get(9)?,
+                next_retry_at: row.get(10)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query jobs: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect jobs: {}", e))
+}
+
+#[tauri::command]
+fn get_retryable_jobs(state: State<'_, AppState>) -> Result<Vec<JobInfo>, String> {
+    get_retryable_jobs_inner(&state)
+}
+
+/// Cheap status histogram for the Queue Board: a single `GROUP BY` query
+/// instead of pulling every job row just to count them, with the oldest
+/// `created_at` per status for age-based pressure. Backs the
+/// `get_job_counts` command.
+fn get_job_count_buckets_inner(state: &AppState) -> Result<HashMap<String, JobCountBucket>, String> {
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
+
+    let mut stmt = conn
+        .prepare("SELECT status, COUNT(*), MIN(created_at) FROM jobs GROUP BY status")
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            let status: String = row.get(0)?;
+            let count: i64 = row.get(1)?;
+            let oldest_created_at: Option<String> = row.get(2)?;
+            Ok((
+                status,
+                JobCountBucket {
+                    count: count as u64,
+                    oldest_created_at,
+                },
+            ))
+        })
+        .map_err(|e| format!("Failed to query job counts: {}", e))?;
+
+    rows.collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| format!("Failed to collect job counts: {}", e))
+}
+
+#[tauri::command]
+fn get_job_counts(state: State<'_, AppState>) -> Result<HashMap<String, JobCountBucket>, String> {
+    get_job_count_buckets_inner(&state)
+}
+
+/// Sums the buckets that `build_queue_snapshot` would classify as
+/// "pending" (i.e. every status that isn't verified/failed/review/running),
+/// without loading a single job row — the aggregate-only equivalent used
+/// by the queue-pressure alert.
+fn pending_count_from_buckets(buckets: &HashMap<String, JobCountBucket>) -> u64 {
+    buckets
+        .iter()
+        .filter(|(status, _)| {
+            !matches!(
+                status.to_lowercase().as_str(),
+                "verified"
+                    | "failed"
+                    | "review_ready"
+                    | "needs_attention"
+                    | "running"
+                    | "round_1_done"
+                    | "round_2_done"
+                    | "round_3_done"
+            )
+        })
+        .map(|(_, bucket)| bucket.count)
+        .sum()
+}
+
+/// Best-effort: after a successful `verify` step, ask the dispatcher to
+/// retry any job whose backoff window has elapsed, so the backlog drains
+/// automatically instead of waiting for an operator to notice.
+fn auto_drain_retry_backlog(state: &AppState) {
+    let due = match get_retryable_jobs_inner(state) {
+        Ok(jobs) => jobs,
+        Err(_) => return,
+    };
+    for job in due {
+        let _ = run_dispatcher_json(state, &["retry", "--job-id", job.job_id.as_str()]);
+    }
+}
+
+fn load_alert_state_snapshot(path: &str) -> AlertStateSnapshot {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return AlertStateSnapshot::default(),
+    };
+
+    if let Ok(snapshot) = serde_json::from_str::<AlertStateSnapshot>(&content) {
+        return snapshot;
+    }
+
+    if let Ok(legacy_ack_ids) = serde_json::from_str::<Vec<String>>(&content) {
+        return AlertStateSnapshot {
+            acknowledged_ids: legacy_ack_ids.into_iter().collect(),
+            ..AlertStateSnapshot::default()
+        };
+    }
+
+    AlertStateSnapshot::default()
+}
+
+fn persist_alert_state_snapshot(path: &str, snapshot: &AlertStateSnapshot) -> Result<(), String> {
+    let path_buf = PathBuf::from(path);
+    if let Some(parent) = path_buf.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to prepare alert state dir: {}", e))?;
+    }
+
+    let payload = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| format!("Failed to serialize alert state: {}", e))?;
+    fs::write(path, payload).map_err(|e| format!("Failed to persist alert state: {}", e))?;
+    Ok(())
+}
+
+fn default_warning_to_critical_minutes() -> u32 {
+    30
+}
+
+fn default_worker_concurrency() -> u32 {
+    1
+}
+
+fn default_stuck_job_minutes() -> u32 {
+    20
+}
+
+fn default_alert_policy_config() -> AlertPolicyConfig {
+    AlertPolicyConfig {
+        warning_to_critical_minutes: default_warning_to_critical_minutes(),
+        stuck_job_minutes: default_stuck_job_minutes(),
+        worker_concurrency: default_worker_concurrency(),
+        runbooks: vec![
+            AlertRunbookRuleConfig {
+                source: Some("service".to_string()),
+                severity: None,
+                headline: "Service health issue".to_string(),
+                steps: vec![
+                    "Open Service Control and confirm which process is stopped or degraded."
+                        .to_string(),
+                    "Restart the affected service, then verify status returns to running."
+                        .to_string(),
+                    "Open Technical Logs and confirm new ERROR lines stop increasing.".to_string(),
+                    "Return to Overview and confirm open alerts and backlog begin to drop."
+                        .to_string(),
+                ],
+                actions: vec![
+                    AlertRunbookAction {
+                        label: "Open Service Control".to_string(),
                         tab: "services".to_string(),
                     },
                     AlertRunbookAction {
                         label: "Open Technical Logs".to_string(),
                         tab: "logs".to_string(),
                     },
+                    AlertRunbookAction {
+                        label: "Open Overview".to_string(),
+                        tab: "dashboard".to_string(),
+                    },
                 ],
             },
             AlertRunbookRuleConfig {
-                source: Some("logs".to_string()),
+                source: Some("jobs".to_string()),
                 severity: None,
-                headline: "Error log surge".to_string(),
+                headline: "Job failure cluster".to_string(),
                 steps: vec![
-                    "Open Technical Logs and identify the most frequent repeating error."
-                        .to_string(),
-                    "Decide whether it is transient (rate-limited) or persistent (input/config)."
+                    "Open Task Center and inspect the most recent failed jobs first.".to_string(),
+                    "Check whether failures share the same source file, sender, or task type."
                         .to_string(),
-                    "Apply fix or restart service, then verify error frequency declines."
+                    "If failures repeat, verify services and logs before rerunning jobs."
                         .to_string(),
+                    "Monitor recovery in Overview success rate and open alerts.".to_string(),
                 ],
                 actions: vec![
+                    AlertRunbookAction {
+                        label: "Open Task Center".to_string(),
+                        tab: "jobs".to_string(),
+                    },
                     AlertRunbookAction {
                         label: "Open Technical Logs".to_string(),
                         tab: "logs".to_string(),
                     },
                     AlertRunbookAction {
-                        label: "Open Service Control".to_string(),
-                        tab: "services".to_string(),
+                        label: "Open Overview".to_string(),
+                        tab: "dashboard".to_string(),
                     },
                 ],
             },
             AlertRunbookRuleConfig {
-                source: None,
-                severity: Some("critical".to_string()),
-                headline: "Critical system signal".to_string(),
+                source: Some("verify".to_string()),
+                severity: None,
+                headline: "Review queue accumulation".to_string(),
                 steps: vec![
-                    "Stabilize service availability first, then reduce queue pressure.".to_string(),
-                    "Inspect logs for persistent failures and verify recovery after mitigation."
+                    "Open Review Desk and prioritize the oldest review_ready jobs first."
                         .to_string(),
-                    "Acknowledge the alert only after impact is contained.".to_string(),
+                    "Process urgent customer-facing files before batch jobs.".to_string(),
+                    "Confirm reviewed jobs leave the queue and no new blockers appear.".to_string(),
                 ],
                 actions: vec![
                     AlertRunbookAction {
-                        label: "Open Service Control".to_string(),
-                        tab: "services".to_string(),
-                    },
-                    AlertRunbookAction {
-                        label: "Open Technical Logs".to_string(),
-                        tab: "logs".to_string(),
+                        label: "Open Review Desk".to_string(),
+                        tab: "verify".to_string(),
                     },
                     AlertRunbookAction {
-                        label: "Open Overview".to_string(),
-                        tab: "dashboard".to_string(),
+                        label: "Open Task Center".to_string(),
+                        tab: "jobs".to_string(),
                     },
                 ],
             },
             AlertRunbookRuleConfig {
-                source: None,
+                source: Some("queue".to_string()),
                 severity: None,
-                headline: "Operational signal".to_string(),
+                headline: "Pending queue pressure".to_string(),
                 steps: vec![
-                    "Open Overview and verify trend direction for related metrics.".to_string(),
-                    "Use Task Center or Logs to isolate root cause and impact scope.".to_string(),
-                    "Acknowledge or ignore only after decision and follow-up action are clear."
+                    "Open Overview Queue Board and identify where jobs accumulate.".to_string(),
+                    "If pending is high, verify worker health and processing throughput."
+                        .to_string(),
+                    "If running is high for too long, inspect logs for retries or API errors."
                         .to_string(),
                 ],
                 actions: vec![
@@ -3563,8 +7147,8 @@ fn default_alert_policy_config() -> AlertPolicyConfig {
                         tab: "dashboard".to_string(),
                     },
                     AlertRunbookAction {
-                        label: "Open Task Center".to_string(),
-                        tab: "jobs".to_string(),
+                        label: "Open Service Control".to_string(),
+                        tab: "services".to_string(),
                     },
                     AlertRunbookAction {
                         label: "Open Technical Logs".to_string(),
@@ -3572,9 +7156,82 @@ fn default_alert_policy_config() -> AlertPolicyConfig {
                     },
                 ],
             },
-        ],
-    }
-}
+            AlertRunbookRuleConfig {
+                source: Some("logs".to_string()),
+                severity: None,
+                headline: "Error log surge".to_string(),
+                steps: vec![
+                    "Open Technical Logs and identify the most frequent repeating error."
+                        .to_string(),
+                    "Decide whether it is transient (rate-limited) or persistent (input/config)."
+                        .to_string(),
+                    "Apply fix or restart service, then verify error frequency declines."
+                        .to_string(),
+                ],
+                actions: vec![
+                    AlertRunbookAction {
+                        label: "Open Technical Logs".to_string(),
+                        tab: "logs".to_string(),
+                    },
+                    AlertRunbookAction {
+                        label: "Open Service Control".to_string(),
+                        tab: "services".to_string(),
+                    },
+                ],
+            },
+            AlertRunbookRuleConfig {
+                source: None,
+                severity: Some("critical".to_string()),
+                headline: "Critical system signal".to_string(),
+                steps: vec![
+                    "Stabilize service availability first, then reduce queue pressure.".to_string(),
+                    "Inspect logs for persistent failures and verify recovery after mitigation."
+                        .to_string(),
+                    "Acknowledge the alert only after impact is contained.".to_string(),
+                ],
+                actions: vec![
+                    AlertRunbookAction {
+                        label: "Open Service Control".to_string(),
+                        tab: "services".to_string(),
+                    },
+                    AlertRunbookAction {
+                        label: "Open Technical Logs".to_string(),
+                        tab: "logs".to_string(),
+                    },
+                    AlertRunbookAction {
+                        label: "Open Overview".to_string(),
+                        tab: "dashboard".to_string(),
+                    },
+                ],
+            },
+            AlertRunbookRuleConfig {
+                source: None,
+                severity: None,
+                headline: "Operational signal".to_string(),
+                steps: vec![
+                    "Open Overview and verify trend direction for related metrics.".to_string(),
+                    "Use Task Center or Logs to isolate root cause and impact scope.".to_string(),
+                    "Acknowledge or ignore only after decision and follow-up action are clear."
+                        .to_string(),
+                ],
+                actions: vec![
+                    AlertRunbookAction {
+                        label: "Open Overview".to_string(),
+                        tab: "dashboard".to_string(),
+                    },
+                    AlertRunbookAction {
+                        label: "Open Task Center".to_string(),
+                        tab: "jobs".to_string(),
+                    },
+                    AlertRunbookAction {
+                        label: "Open Technical Logs".to_string(),
+                        tab: "logs".to_string(),
+                    },
+                ],
+            },
+        ],
+    }
+}
 
 fn load_alert_policy_config(state: &AppState) -> AlertPolicyConfig {
     let mut config = default_alert_policy_config();
@@ -3589,6 +7246,12 @@ fn load_alert_policy_config(state: &AppState) -> AlertPolicyConfig {
         if parsed.warning_to_critical_minutes > 0 {
             config.warning_to_critical_minutes = parsed.warning_to_critical_minutes;
         }
+        if parsed.stuck_job_minutes > 0 {
+            config.stuck_job_minutes = parsed.stuck_job_minutes;
+        }
+        if parsed.worker_concurrency > 0 {
+            config.worker_concurrency = parsed.worker_concurrency;
+        }
         if !parsed.runbooks.is_empty() {
             config.runbooks = parsed.runbooks;
         }
@@ -3692,6 +7355,16 @@ fn parse_timestamp_local(ts: &str) -> Option<DateTime<Local>> {
 }
 
 fn read_log_file_inner(state: &AppState, service: &str, lines: u32) -> Result<Vec<String>, String> {
+    let service_display_name = match service {
+        "telegram" => "Telegram Bot",
+        "worker" => "Run Worker",
+        _ => service,
+    };
+    if let ServiceBackend::Docker { container } = service_backend_for(state, service_display_name)
+    {
+        return docker_container_log_tail(&container, lines);
+    }
+
     let log_file = match service {
         "telegram" => PathBuf::from(&state.logs_dir).join("telegram.log"),
         "worker" => PathBuf::from(&state.logs_dir).join("worker.log"),
@@ -3710,14 +7383,16 @@ fn read_log_file_inner(state: &AppState, service: &str, lines: u32) -> Result<Ve
 }
 
 fn load_recent_jobs(state: &AppState, limit: u32) -> Result<Vec<JobInfo>, String> {
-    use rusqlite::Connection;
-
-    let conn =
-        Connection::open(&state.db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
+    ensure_job_retry_columns(&conn).map_err(|e| format!("Failed to migrate jobs table: {}", e))?;
+    ensure_job_attempt_columns(&conn).map_err(|e| format!("Failed to migrate jobs table: {}", e))?;
 
     let mut stmt = conn
         .prepare(
-            "SELECT job_id, status, task_type, sender, created_at, updated_at
+            "SELECT job_id, status, task_type, sender, created_at, updated_at, error_count, last_try, next_try, attempt_count, next_retry_at
          FROM jobs
          ORDER BY created_at DESC
          LIMIT ?1",
@@ -3733,6 +7408,11 @@ fn load_recent_jobs(state: &AppState, limit: u32) -> Result<Vec<JobInfo>, String
                 sender: row.get(3)?,
                 created_at: row.get(4)?,
                 updated_at: row.get(5)?,
+                error_count: row.get(6)?,
+                last_try: row.get(7)?,
+                next_try: row.get(8)?,
+                attempt_count: row.get(9)?,
+                next_retry_at: row.get(10)?,
             })
         })
         .map_err(|e| format!("Failed to query jobs: {}", e))?;
@@ -3785,7 +7465,7 @@ fn push_alert_item(
     id: String,
     title: String,
     message: String,
-    severity: &str,
+    severity: AlertSeverity,
     source: &str,
     metric: Option<i64>,
     action: Option<String>,
@@ -3794,20 +7474,20 @@ fn push_alert_item(
         .first_seen_ms
         .entry(id.clone())
         .or_insert(now_ms);
-    let mut resolved_severity = severity.to_string();
+    let mut resolved_severity = severity;
     let mut resolved_message = message;
     let status = if alert_state.ignored_ids.contains(&id) {
-        "ignored"
+        AlertStatus::Ignored
     } else if alert_state.acknowledged_ids.contains(&id) {
-        "acknowledged"
+        AlertStatus::Acknowledged
     } else {
-        "open"
+        AlertStatus::Open
     };
 
-    if status == "open" && severity.eq_ignore_ascii_case("warning") {
+    if status == AlertStatus::Open && severity == AlertSeverity::Warning {
         let escalation_ms = (warning_to_critical_minutes as i64) * 60_000;
         if escalation_ms > 0 && now_ms.saturating_sub(first_seen) >= escalation_ms {
-            resolved_severity = "critical".to_string();
+            resolved_severity = AlertSeverity::Critical;
             resolved_message = format!(
                 "{} Escalated to critical after {} minutes unresolved.",
                 resolved_message, warning_to_critical_minutes
@@ -3820,7 +7500,7 @@ fn push_alert_item(
         title,
         message: resolved_message,
         severity: resolved_severity,
-        status: status.to_string(),
+        status,
         source: source.to_string(),
         metric_value: metric,
         created_at: first_seen,
@@ -3860,7 +7540,7 @@ fn build_alerts(
                 alert_id,
                 format!("{} is not running", service.name),
                 "Service health is degraded. Start or restart this service.".to_string(),
-                "critical",
+                AlertSeverity::Critical,
                 "service",
                 None,
                 Some("Open Service Control".to_string()),
@@ -3874,9 +7554,9 @@ fn build_alerts(
         .count() as i64;
     if failed_jobs > 0 {
         let sev = if failed_jobs >= 5 {
-            "critical"
+            AlertSeverity::Critical
         } else {
-            "warning"
+            AlertSeverity::Warning
         };
         active_ids.insert("jobs_failed_recent".to_string());
         push_alert_item(
@@ -3904,14 +7584,20 @@ fn build_alerts(
             "review_backlog".to_string(),
             "Review backlog growing".to_string(),
             format!("{} jobs are waiting for review.", queue.review_ready),
-            "warning",
+            AlertSeverity::Warning,
             "verify",
             Some(queue.review_ready as i64),
             Some("Open Verify queue".to_string()),
         );
     }
 
-    if queue.pending >= 10 {
+    // Threshold on the live DB aggregate rather than the period-windowed job
+    // list: a job queued before the overview window started is still a job
+    // sitting in the queue right now.
+    let pending_now = get_job_count_buckets_inner(state)
+        .map(|buckets| pending_count_from_buckets(&buckets))
+        .unwrap_or(queue.pending);
+    if pending_now >= 10 {
         active_ids.insert("queue_pending_high".to_string());
         push_alert_item(
             &mut alerts,
@@ -3920,14 +7606,87 @@ fn build_alerts(
             warning_to_critical_minutes,
             "queue_pending_high".to_string(),
             "Pending queue is high".to_string(),
-            format!("{} jobs are still waiting in the queue.", queue.pending),
-            "warning",
+            format!("{} jobs are still waiting in the queue.", pending_now),
+            AlertSeverity::Warning,
             "queue",
-            Some(queue.pending as i64),
+            Some(pending_now as i64),
             Some("Check queue board".to_string()),
         );
     }
 
+    // Jobs claimed but not progressing are the most dangerous silent
+    // failure: they don't show up as "failed", just quietly stop moving.
+    let stuck_job_minutes = policy.stuck_job_minutes.max(1) as i64;
+    let stuck_threshold_ms = stuck_job_minutes * 60_000;
+    let stuck_staleness_ms: Vec<i64> = jobs
+        .iter()
+        .filter(|job| {
+            matches!(
+                job.status.to_lowercase().as_str(),
+                "running" | "round_1_done" | "round_2_done" | "round_3_done"
+            )
+        })
+        .filter_map(|job| parse_timestamp_local(&job.updated_at))
+        .map(|updated_at| now_ms.saturating_sub(updated_at.timestamp_millis()))
+        .filter(|staleness_ms| *staleness_ms >= stuck_threshold_ms)
+        .collect();
+
+    if !stuck_staleness_ms.is_empty() {
+        let stuck_count = stuck_staleness_ms.len();
+        let worst_staleness_ms = stuck_staleness_ms.iter().copied().max().unwrap_or(0);
+        active_ids.insert("jobs_stuck".to_string());
+        push_alert_item(
+            &mut alerts,
+            &mut alert_state,
+            now_ms,
+            warning_to_critical_minutes,
+            "jobs_stuck".to_string(),
+            "Jobs appear stuck".to_string(),
+            format!(
+                "{} job(s) have been in progress with no update for over {} minutes (worst: {} minutes).",
+                stuck_count,
+                stuck_job_minutes,
+                worst_staleness_ms / 60_000
+            ),
+            if stuck_count >= 3 {
+                AlertSeverity::Critical
+            } else {
+                AlertSeverity::Warning
+            },
+            "jobs",
+            Some(worst_staleness_ms),
+            Some("Inspect stuck jobs".to_string()),
+        );
+    }
+
+    // Jobs requeue_failed_jobs has given up on entirely, distinct from the
+    // ordinary "some jobs failed this period" alert above.
+    let exhausted_jobs = jobs
+        .iter()
+        .filter(|j| {
+            j.status.eq_ignore_ascii_case("failed") && j.attempt_count >= FAILED_REQUEUE_MAX_ATTEMPTS
+        })
+        .count() as i64;
+    if exhausted_jobs > 0 {
+        active_ids.insert("jobs_retry_exhausted".to_string());
+        push_alert_item(
+            &mut alerts,
+            &mut alert_state,
+            now_ms,
+            warning_to_critical_minutes,
+            "jobs_retry_exhausted".to_string(),
+            "Jobs have exhausted their retries".to_string(),
+            format!(
+                "{} job(s) failed {} or more times and are no longer being automatically requeued.",
+                exhausted_jobs, FAILED_REQUEUE_MAX_ATTEMPTS
+            ),
+            AlertSeverity::Critical,
+            "jobs",
+            Some(exhausted_jobs),
+            Some("Review failed jobs".to_string()),
+        );
+    }
+
     if let Ok(worker_lines) = read_log_file_inner(state, "worker", 200) {
         let err_count = worker_lines
             .iter()
@@ -3948,9 +7707,9 @@ fn build_alerts(
                 "Worker error logs found".to_string(),
                 format!("{} error-level log lines found recently.", err_count),
                 if err_count >= 10 {
-                    "critical"
+                    AlertSeverity::Critical
                 } else {
-                    "warning"
+                    AlertSeverity::Warning
                 },
                 "logs",
                 Some(err_count),
@@ -3969,7 +7728,7 @@ fn build_alerts(
             "system_nominal".to_string(),
             "No active issues".to_string(),
             "System is healthy. Continue routine monitoring.".to_string(),
-            "info",
+            AlertSeverity::Info,
             "system",
             None,
             None,
@@ -3977,20 +7736,20 @@ fn build_alerts(
     }
 
     alerts.sort_by(|a, b| {
-        let weight = |sev: &str| match sev {
-            "critical" => 0,
-            "warning" => 1,
+        let weight = |sev: AlertSeverity| match sev {
+            AlertSeverity::Critical => 0,
+            AlertSeverity::Warning => 1,
             _ => 2,
         };
-        let status_weight = |status: &str| match status {
-            "open" => 0,
-            "acknowledged" => 1,
+        let status_weight = |status: AlertStatus| match status {
+            AlertStatus::Open => 0,
+            AlertStatus::Acknowledged => 1,
             _ => 2,
         };
-        let sa = status_weight(&a.status);
-        let sb = status_weight(&b.status);
+        let sa = status_weight(a.status);
+        let sb = status_weight(b.status);
         sa.cmp(&sb)
-            .then(weight(&a.severity).cmp(&weight(&b.severity)))
+            .then(weight(a.severity).cmp(&weight(b.severity)))
             .then(a.created_at.cmp(&b.created_at))
     });
 
@@ -4054,11 +7813,27 @@ fn build_overview_data(
         0.0
     };
 
+    let worker_concurrency = load_alert_policy_config(state).worker_concurrency.max(1) as f64;
+    let period_start = now_epoch_ms() / 1000 - (period_hours as i64 * 3600);
+    let busy_secs: i64 = jobs
+        .iter()
+        .filter_map(|job| {
+            let created = parse_timestamp_local(&job.created_at)?.timestamp();
+            let updated = parse_timestamp_local(&job.updated_at)?.timestamp();
+            let overlap_start = created.max(period_start);
+            let overlap_end = updated.max(created);
+            Some((overlap_end - overlap_start).max(0))
+        })
+        .sum();
+    let occupancy_rate = ((busy_secs as f64 / (period_hours as f64 * 3600.0 * worker_concurrency))
+        * 100.0)
+        .min(100.0);
+
     let services_running = services.iter().filter(|s| s.status == "running").count() as u64;
     let services_total = services.len() as u64;
     let open_alerts = alerts
         .iter()
-        .filter(|a| a.status == "open" && a.id != "system_nominal")
+        .filter(|a| a.status == AlertStatus::Open && a.id != "system_nominal")
         .count() as u64;
 
     let metrics = OverviewMetrics {
@@ -4070,6 +7845,7 @@ fn build_overview_data(
         backlog_jobs: queue.pending + queue.running + queue.review_ready,
         success_rate,
         avg_turnaround_minutes,
+        occupancy_rate,
         services_running,
         services_total,
         open_alerts,
@@ -4090,6 +7866,16 @@ fn get_overview_metrics(
     Ok(metrics)
 }
 
+/// Seconds of `[span_start, span_end]` that fall inside the hourly window
+/// starting at `bucket_start` and running 3600 seconds. Used to turn a
+/// job's `[created_at, updated_at]` lifetime into per-bucket worker-busy time.
+fn bucket_overlap_seconds(span_start: i64, span_end: i64, bucket_start: i64) -> i64 {
+    let bucket_end = bucket_start + 3600;
+    let overlap_start = span_start.max(bucket_start);
+    let overlap_end = span_end.min(bucket_end);
+    (overlap_end - overlap_start).max(0)
+}
+
 #[tauri::command]
 fn get_overview_trends(
     metric: String,
@@ -4102,6 +7888,41 @@ fn get_overview_trends(
     let now = Local::now().timestamp();
     let current_bucket = now - (now % 3600);
 
+    if metric_key == "occupancy" {
+        let worker_concurrency = load_alert_policy_config(&state).worker_concurrency.max(1) as f64;
+        let spans: Vec<(i64, i64)> = jobs
+            .iter()
+            .filter_map(|job| {
+                let created = parse_timestamp_local(&job.created_at)?.timestamp();
+                let updated = parse_timestamp_local(&job.updated_at)?.timestamp();
+                Some((created, updated.max(created)))
+            })
+            .collect();
+
+        let mut points = Vec::new();
+        for idx in 0..period {
+            let bucket = current_bucket - ((period - 1 - idx) as i64 * 3600);
+            let busy_secs: i64 = spans
+                .iter()
+                .map(|(start, end)| bucket_overlap_seconds(*start, *end, bucket))
+                .sum();
+            let occupancy_pct = ((busy_secs as f64 / (3600.0 * worker_concurrency)) * 100.0)
+                .min(100.0)
+                .round() as i64;
+            let label = Local
+                .timestamp_opt(bucket, 0)
+                .single()
+                .map(|d| d.format("%m-%d %H:00").to_string())
+                .unwrap_or_else(|| bucket.to_string());
+            points.push(TrendPoint {
+                timestamp: bucket * 1000,
+                label,
+                value: occupancy_pct,
+            });
+        }
+        return Ok(points);
+    }
+
     let mut buckets: HashMap<i64, i64> = HashMap::new();
     for job in jobs {
         let use_job = match metric_key.as_str() {
@@ -4160,11 +7981,11 @@ fn list_alerts(
     let (_, _, mut alerts, _) = build_overview_data(&state, 24)?;
     if let Some(status_filter) = status {
         let sf = status_filter.to_lowercase();
-        alerts.retain(|a| a.status.to_lowercase() == sf);
+        alerts.retain(|a| a.status.as_str() == sf);
     }
     if let Some(sev_filter) = severity {
         let sev = sev_filter.to_lowercase();
-        alerts.retain(|a| a.severity.to_lowercase() == sev);
+        alerts.retain(|a| a.severity.as_str() == sev);
     }
     Ok(alerts)
 }
@@ -4272,6 +8093,243 @@ fn get_alert_runbook(
     Ok(resolve_alert_runbook(&policy, &source, &severity))
 }
 
+/// Escapes a Prometheus label value per the text exposition format (quotes,
+/// backslashes, and newlines must be escaped inside the quoted label value).
+fn prometheus_escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Renders the same data `build_overview_data`/`export_run_summary` already
+/// produce as a Prometheus text-exposition body, so the existing collectors
+/// become scrapable without changing what they compute.
+fn render_overview_prometheus_metrics(
+    metrics: &OverviewMetrics,
+    queue: &QueueSnapshot,
+    services: &[ServiceStatus],
+    alerts: &[AlertItem],
+) -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP translation_jobs_total Total jobs observed in the scrape window.\n");
+    out.push_str("# TYPE translation_jobs_total gauge\n");
+    out.push_str(&format!("translation_jobs_total {}\n", metrics.total_jobs));
+
+    out.push_str(
+        "# HELP translation_jobs_completed Jobs that finished successfully in the scrape window.\n",
+    );
+    out.push_str("# TYPE translation_jobs_completed gauge\n");
+    out.push_str(&format!(
+        "translation_jobs_completed {}\n",
+        metrics.completed_jobs
+    ));
+
+    out.push_str("# HELP translation_jobs_failed Jobs that failed in the scrape window.\n");
+    out.push_str("# TYPE translation_jobs_failed gauge\n");
+    out.push_str(&format!("translation_jobs_failed {}\n", metrics.failed_jobs));
+
+    out.push_str(
+        "# HELP translation_success_rate Percentage of processed jobs that completed successfully.\n",
+    );
+    out.push_str("# TYPE translation_success_rate gauge\n");
+    out.push_str(&format!(
+        "translation_success_rate {}\n",
+        metrics.success_rate
+    ));
+
+    out.push_str(
+        "# HELP translation_avg_turnaround_minutes Average job turnaround time in minutes.\n",
+    );
+    out.push_str("# TYPE translation_avg_turnaround_minutes gauge\n");
+    out.push_str(&format!(
+        "translation_avg_turnaround_minutes {}\n",
+        metrics.avg_turnaround_minutes
+    ));
+
+    out.push_str("# HELP translation_queue_jobs Jobs currently in each queue state.\n");
+    out.push_str("# TYPE translation_queue_jobs gauge\n");
+    for (state_label, value) in [
+        ("pending", queue.pending),
+        ("running", queue.running),
+        ("review_ready", queue.review_ready),
+        ("done", queue.done),
+        ("failed", queue.failed),
+    ] {
+        out.push_str(&format!(
+            "translation_queue_jobs{{state=\"{}\"}} {}\n",
+            state_label, value
+        ));
+    }
+
+    out.push_str(
+        "# HELP translation_service_up Whether a managed service is currently running.\n",
+    );
+    out.push_str("# TYPE translation_service_up gauge\n");
+    for service in services {
+        let up = if service.status == "running" { 1 } else { 0 };
+        out.push_str(&format!(
+            "translation_service_up{{service=\"{}\"}} {}\n",
+            prometheus_escape_label(&service.name),
+            up
+        ));
+    }
+
+    out.push_str("# HELP translation_open_alerts Currently open alerts by severity.\n");
+    out.push_str("# TYPE translation_open_alerts gauge\n");
+    for severity in [
+        AlertSeverity::Critical,
+        AlertSeverity::Warning,
+        AlertSeverity::Info,
+    ] {
+        let count = alerts
+            .iter()
+            .filter(|a| a.status == AlertStatus::Open && a.severity == severity)
+            .count();
+        out.push_str(&format!(
+            "translation_open_alerts{{severity=\"{}\"}} {}\n",
+            severity.as_str(),
+            count
+        ));
+    }
+
+    out
+}
+
+fn api_auth_type_label(auth_type: ApiAuthType) -> &'static str {
+    match auth_type {
+        ApiAuthType::Oauth => "oauth",
+        ApiAuthType::ApiKey => "api_key",
+        ApiAuthType::NoAuth => "none",
+        ApiAuthType::Unknown => "unknown",
+    }
+}
+
+/// Renders provider health/usage data (`ApiProvider`/`ProviderActivity`/
+/// `ApiUsage`, already built for the dashboard) as a Prometheus text-
+/// exposition body, so a scraper can alert on provider auth/quota without
+/// polling the dashboard commands directly.
+fn render_provider_prometheus_metrics(
+    providers: &[ApiProvider],
+    activities: &HashMap<String, ProviderActivity>,
+    openrouter_usage: Option<&ApiUsage>,
+) -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP provider_configured Whether a provider has a usable credential configured (1) or not (0).\n");
+    out.push_str("# TYPE provider_configured gauge\n");
+    for provider in providers {
+        let configured = if provider.status == ApiProviderStatus::Configured { 1 } else { 0 };
+        out.push_str(&format!(
+            "provider_configured{{provider=\"{}\",auth_type=\"{}\"}} {}\n",
+            prometheus_escape_label(&provider.id),
+            api_auth_type_label(provider.auth_type),
+            configured
+        ));
+    }
+
+    out.push_str("# HELP provider_oauth_expires_seconds Unix timestamp (seconds) the provider's OAuth token expires at.\n");
+    out.push_str("# TYPE provider_oauth_expires_seconds gauge\n");
+    for provider in providers {
+        if provider.auth_type != ApiAuthType::Oauth {
+            continue;
+        }
+        if let Some(expires_at) = provider.expires_at {
+            out.push_str(&format!(
+                "provider_oauth_expires_seconds{{provider=\"{}\"}} {}\n",
+                prometheus_escape_label(&provider.id),
+                expires_at / 1000
+            ));
+        }
+    }
+
+    out.push_str("# HELP provider_calls_total Calls observed for the provider in the last 24h.\n");
+    out.push_str("# TYPE provider_calls_total gauge\n");
+    out.push_str("# HELP provider_errors_total Errored calls observed for the provider in the last 24h.\n");
+    out.push_str("# TYPE provider_errors_total gauge\n");
+    out.push_str("# HELP provider_success_rate Share of calls that did not error for the provider in the last 24h.\n");
+    out.push_str("# TYPE provider_success_rate gauge\n");
+    let mut provider_ids: Vec<&String> = activities.keys().collect();
+    provider_ids.sort();
+    for provider_id in provider_ids {
+        let activity = &activities[provider_id];
+        out.push_str(&format!(
+            "provider_calls_total{{provider=\"{}\"}} {}\n",
+            prometheus_escape_label(provider_id),
+            activity.calls
+        ));
+        out.push_str(&format!(
+            "provider_errors_total{{provider=\"{}\"}} {}\n",
+            prometheus_escape_label(provider_id),
+            activity.errors
+        ));
+        let success_rate = if activity.calls > 0 {
+            (activity.calls - activity.errors) as f64 / activity.calls as f64
+        } else {
+            0.0
+        };
+        out.push_str(&format!(
+            "provider_success_rate{{provider=\"{}\"}} {}\n",
+            prometheus_escape_label(provider_id),
+            success_rate
+        ));
+    }
+
+    if let Some(usage) = openrouter_usage {
+        if usage.unit == "credits" && matches!(usage.source, ApiUsageSource::RealApi) {
+            out.push_str("# HELP openrouter_credits_remaining Remaining OpenRouter credits, as reported by the /auth/key endpoint.\n");
+            out.push_str("# TYPE openrouter_credits_remaining gauge\n");
+            out.push_str(&format!("openrouter_credits_remaining {}\n", usage.remaining));
+            out.push_str("# HELP openrouter_credits_used OpenRouter credits used, as reported by the /auth/key endpoint.\n");
+            out.push_str("# TYPE openrouter_credits_used gauge\n");
+            out.push_str(&format!("openrouter_credits_used {}\n", usage.used));
+        }
+    }
+
+    out
+}
+
+#[tauri::command]
+fn get_prometheus_metrics(
+    range_hours: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let period = range_hours.unwrap_or(24).clamp(1, 24 * 14);
+    let (metrics, queue, alerts, _) = build_overview_data(&state, period)?;
+    let services = get_service_status_inner(&state).map_err(|e| e.to_string())?;
+    Ok(render_overview_prometheus_metrics(&metrics, &queue, &services, &alerts))
+}
+
+/// Scrapable provider health/usage snapshot: auth status, OAuth expiry,
+/// 24h call/error counts, and (when an OpenRouter key is configured and its
+/// `/auth/key` call succeeds) the live OpenRouter credit balance. Reuses the same data the dashboard's
+/// provider cards already compute, with no refresh side effects so a
+/// frequent scrape interval can't spam OAuth token endpoints.
+#[tauri::command]
+async fn get_metrics(state: State<'_, AppState>) -> Result<String, String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+
+    let profiles = load_auth_profiles_migrated()?;
+    let providers = compute_api_providers(&profiles, now);
+
+    let mut activities = HashMap::new();
+    for (id, _name, _auth_type) in get_known_providers() {
+        activities.insert(id.to_string(), estimate_provider_activity(&state, id, 24));
+    }
+
+    let openrouter_usage = get_api_usage_inner(&state, "openrouter").await?;
+
+    Ok(render_provider_prometheus_metrics(
+        &providers,
+        &activities,
+        openrouter_usage.as_ref(),
+    ))
+}
+
 #[tauri::command]
 fn export_run_summary(
     date: Option<String>,
@@ -4290,7 +8348,7 @@ fn export_run_summary(
 
     let open_alerts: Vec<&AlertItem> = alerts
         .iter()
-        .filter(|a| a.status == "open" && a.id != "system_nominal")
+        .filter(|a| a.status == AlertStatus::Open && a.id != "system_nominal")
         .take(3)
         .collect();
 
@@ -4321,12 +8379,21 @@ fn export_run_summary(
         for alert in open_alerts {
             lines.push(format!(
                 "   [{}] {}",
-                alert.severity.to_uppercase(),
+                alert.severity.as_str().to_uppercase(),
                 alert.title
             ));
         }
     }
 
+    if let Some(summary) = state
+        .reconciliation_summary
+        .lock()
+        .ok()
+        .and_then(|s| s.clone())
+    {
+        lines.push(format!("- Startup reconciliation: {}", summary));
+    }
+
     Ok(RunSummary {
         date: date_str,
         text: lines.join("\n"),
@@ -4382,14 +8449,13 @@ fn list_verify_artifacts(
     Ok(artifacts)
 }
 
-#[tauri::command]
-fn get_quality_report(
-    job_id: String,
-    state: State<'_, AppState>,
+fn get_quality_report_inner(
+    state: &AppState,
+    job_id: &str,
 ) -> Result<Option<QualityReport>, String> {
-    let config = get_config_inner(&state)?;
+    let config = get_config_inner(state)?;
     let path = verify_root(&config.work_root)
-        .join(&job_id)
+        .join(job_id)
         .join(".system")
         .join("quality_report.json");
     if !path.exists() {
@@ -4436,6 +8502,57 @@ fn get_quality_report(
     }))
 }
 
+#[tauri::command]
+fn get_quality_report(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<QualityReport>, String> {
+    get_quality_report_inner(&state, &job_id)
+}
+
+/// Blocks until `job_id`'s `quality_report.json` is newer than
+/// `since_generated_at` (ms since epoch) or `timeout_ms` elapses, so the
+/// quality panel updates the moment a new round's metrics land instead of
+/// refetching on a fixed interval.
+#[tauri::command]
+async fn poll_quality_report(
+    state: State<'_, AppState>,
+    job_id: String,
+    since_generated_at: Option<i64>,
+    timeout_ms: Option<u64>,
+) -> Result<QualityReportPollResult, String> {
+    let config = get_config_inner(&state)?;
+    let path = verify_root(&config.work_root)
+        .join(&job_id)
+        .join(".system")
+        .join("quality_report.json");
+    let timeout_ms = timeout_ms
+        .unwrap_or(FILE_POLL_DEFAULT_TIMEOUT_MS)
+        .clamp(1_000, FILE_POLL_MAX_TIMEOUT_MS);
+
+    match wait_for_file_modified(&path, since_generated_at, timeout_ms).await {
+        Some(mtime_ms) => Ok(QualityReportPollResult {
+            modified: true,
+            generated_at: Some(mtime_ms),
+            report: get_quality_report_inner(&state, &job_id)?,
+        }),
+        None => Ok(QualityReportPollResult {
+            modified: false,
+            generated_at: None,
+            report: None,
+        }),
+    }
+}
+
+/// Quality report of the most recently updated job that has one, for the
+/// `translation_quality_percent` Prometheus gauge -- unlike `get_quality_report`
+/// this isn't scoped to a single job, since a scrape has no job to ask about.
+fn latest_quality_report_inner(state: &AppState) -> Option<QualityReport> {
+    let jobs = load_recent_jobs(state, 50).ok()?;
+    jobs.iter()
+        .find_map(|job| get_quality_report_inner(state, &job.job_id).ok().flatten())
+}
+
 #[tauri::command]
 fn get_verify_folder_path(state: State<'_, AppState>) -> Result<String, String> {
     let config = get_config_inner(&state)?;
@@ -4471,12 +8588,74 @@ fn get_kb_sync_report(state: State<'_, AppState>) -> Result<Option<KbSyncReport>
     read_kb_sync_report(&config.work_root)
 }
 
+const FILE_POLL_INTERVAL_MS: u64 = 500;
+const FILE_POLL_DEFAULT_TIMEOUT_MS: u64 = 25_000;
+const FILE_POLL_MAX_TIMEOUT_MS: u64 = 60_000;
+
+fn file_mtime_ms(path: &std::path::Path) -> Option<i64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(epoch.as_millis() as i64)
+}
+
+/// Blocks until `path`'s mtime is newer than `since_ms` (any mtime counts as
+/// new if `since_ms` is `None`, i.e. the caller hasn't seen a report yet) or
+/// `timeout_ms` elapses. Returns the observed mtime (ms since epoch) on a
+/// change, `None` on timeout -- the long-poll primitive shared by
+/// `poll_kb_sync` and `poll_quality_report`.
+async fn wait_for_file_modified(
+    path: &std::path::Path,
+    since_ms: Option<i64>,
+    timeout_ms: u64,
+) -> Option<i64> {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(timeout_ms);
+    loop {
+        if let Some(mtime_ms) = file_mtime_ms(path) {
+            if since_ms.map(|since| mtime_ms > since).unwrap_or(true) {
+                return Some(mtime_ms);
+            }
+        }
+        if std::time::Instant::now() >= deadline {
+            return None;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(FILE_POLL_INTERVAL_MS)).await;
+    }
+}
+
+/// Blocks until `kb_sync_latest.json` is newer than `since_generated_at` (ms
+/// since epoch) or `timeout_ms` elapses, instead of the UI busy-polling
+/// `get_kb_sync_report` on a fixed interval.
 #[tauri::command]
-fn get_kb_stats(state: State<'_, AppState>) -> Result<KbStats, String> {
-    use rusqlite::Connection;
+async fn poll_kb_sync(
+    state: State<'_, AppState>,
+    since_generated_at: Option<i64>,
+    timeout_ms: Option<u64>,
+) -> Result<KbSyncPollResult, String> {
+    let config = get_config_inner(&state)?;
+    let path = kb_sync_report_path(&config.work_root);
+    let timeout_ms = timeout_ms
+        .unwrap_or(FILE_POLL_DEFAULT_TIMEOUT_MS)
+        .clamp(1_000, FILE_POLL_MAX_TIMEOUT_MS);
+
+    match wait_for_file_modified(&path, since_generated_at, timeout_ms).await {
+        Some(mtime_ms) => Ok(KbSyncPollResult {
+            modified: true,
+            generated_at: Some(mtime_ms),
+            report: read_kb_sync_report(&config.work_root)?,
+        }),
+        None => Ok(KbSyncPollResult {
+            modified: false,
+            generated_at: None,
+            report: None,
+        }),
+    }
+}
 
-    let conn =
-        Connection::open(&state.db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+fn get_kb_stats_inner(state: &AppState) -> Result<KbStats, String> {
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
 
     let total_files: u64 = conn
         .query_row("SELECT COUNT(*) FROM kb_files", [], |row| row.get(0))
@@ -4519,7 +8698,12 @@ fn get_kb_stats(state: State<'_, AppState>) -> Result<KbStats, String> {
 }
 
 #[tauri::command]
-async fn kb_sync_now(state: State<'_, AppState>) -> Result<KbSyncReport, String> {
+fn get_kb_stats(state: State<'_, AppState>) -> Result<KbStats, String> {
+    get_kb_stats_inner(&state)
+}
+
+#[tauri::command]
+async fn kb_sync_now(state: State<'_, AppState>) -> Result<KbSyncReport, String> {
     let config = get_config_inner(&state)?;
     let python_bin = find_python_bin(&state);
 
@@ -4554,19 +8738,14 @@ async fn kb_sync_now(state: State<'_, AppState>) -> Result<KbSyncReport, String>
     }
 }
 
-#[tauri::command]
-fn list_kb_files(
-    state: State<'_, AppState>,
-    query: Option<String>,
-    source_group: Option<String>,
-    limit: Option<u32>,
-    offset: Option<u32>,
-) -> Result<KbFileList, String> {
+/// Builds the shared `kb_files` filter used by both the paged `list_kb_files`
+/// command and the unpaged `export_kb_files`, so the two never drift apart
+/// on what `query`/`source_group` mean.
+fn kb_files_where_clause(
+    query: &Option<String>,
+    source_group: &Option<String>,
+) -> (String, Vec<rusqlite::types::Value>) {
     use rusqlite::types::Value;
-    use rusqlite::Connection;
-
-    let conn =
-        Connection::open(&state.db_path).map_err(|e| format!("Failed to open database: {}", e))?;
 
     let mut where_clauses: Vec<&'static str> = Vec::new();
     let mut params: Vec<Value> = Vec::new();
@@ -4592,6 +8771,25 @@ fn list_kb_files(
     } else {
         format!("WHERE {}", where_clauses.join(" AND "))
     };
+    (where_sql, params)
+}
+
+#[tauri::command]
+fn list_kb_files(
+    state: State<'_, AppState>,
+    query: Option<String>,
+    source_group: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<KbFileList, String> {
+    use rusqlite::types::Value;
+
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
+
+    let (where_sql, params) = kb_files_where_clause(&query, &source_group);
 
     let total_sql = format!("SELECT COUNT(*) FROM kb_files {}", where_sql);
     let total: u64 = conn
@@ -4640,22 +8838,134 @@ fn list_kb_files(
     Ok(KbFileList { total, items })
 }
 
-fn run_glossary_manager_json(
-    state: &AppState,
-    args: &[String],
-) -> Result<serde_json::Value, String> {
-    let config = get_config_inner(state)?;
-    let python_bin = find_python_bin(state);
+/// Binary-prefixed human-readable size (e.g. `1.2 MiB`), shared by both
+/// `export_kb_files` and `export_glossary_terms` so a byte count reads the
+/// same way in either export.
+fn format_human_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit_idx = 0;
+    while value >= 1024.0 && unit_idx < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit_idx += 1;
+    }
+    if unit_idx == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit_idx])
+    }
+}
 
-    let mut cmd_args = vec!["-m".to_string(), "scripts.glossary_manager".to_string()];
-    cmd_args.extend_from_slice(args);
+/// Quotes a field if it contains the delimiter, a quote, or a newline,
+/// doubling any embedded quotes (RFC 4180, applied to TSV too for
+/// consistency between the two export formats).
+fn tabular_escape_field(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains('"') || field.contains('\n') || field.contains('\r')
+    {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
 
-    let output = Command::new(&python_bin)
-        .args(&cmd_args)
-        .current_dir(&state.config_path)
-        .output()
-        .map_err(|e| format!("Failed to run glossary manager: {}", e))?;
+fn tabular_delimiter(format: &str) -> Result<char, String> {
+    match format.to_lowercase().as_str() {
+        "csv" => Ok(','),
+        "tsv" => Ok('\t'),
+        other => Err(format!(
+            "Unsupported export format: {} (expected \"csv\" or \"tsv\")",
+            other
+        )),
+    }
+}
+
+fn write_tabular_row(out: &mut String, fields: &[String], delimiter: char) {
+    let line = fields
+        .iter()
+        .map(|f| tabular_escape_field(f, delimiter))
+        .collect::<Vec<_>>()
+        .join(&delimiter.to_string());
+    out.push_str(&line);
+    out.push('\n');
+}
+
+/// Streams every `kb_files` row matching `query`/`source_group` to `path` as
+/// CSV or TSV, ignoring the UI page `limit` so auditors can pull the full
+/// corpus in one shot. Returns the number of rows written.
+#[tauri::command]
+fn export_kb_files(
+    state: State<'_, AppState>,
+    format: String,
+    path: String,
+    query: Option<String>,
+    source_group: Option<String>,
+) -> Result<u64, String> {
+    let delimiter = tabular_delimiter(&format)?;
+    let conn = state
+        .db_pool
+        .get()
+        .map_err(|e| format!("Failed to get pooled connection: {}", e))?;
+    let (where_sql, params) = kb_files_where_clause(&query, &source_group);
+
+    let sql = format!(
+        "SELECT path, parser, source_group, chunk_count, indexed_at, size_bytes FROM kb_files {} ORDER BY indexed_at DESC",
+        where_sql
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Failed to query KB files: {}", e))?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+            let chunk_count: i64 = row.get(3)?;
+            let size_bytes: i64 = row.get(5)?;
+            Ok(KbFileRow {
+                path: row.get(0)?,
+                parser: row.get(1)?,
+                source_group: row.get(2)?,
+                chunk_count: std::cmp::max(0, chunk_count) as u64,
+                indexed_at: row.get(4)?,
+                size_bytes: std::cmp::max(0, size_bytes) as u64,
+            })
+        })
+        .map_err(|e| format!("Failed to query KB files: {}", e))?;
+
+    let mut out = String::new();
+    write_tabular_row(
+        &mut out,
+        &[
+            "path".to_string(),
+            "parser".to_string(),
+            "source_group".to_string(),
+            "chunk_count".to_string(),
+            "size".to_string(),
+            "indexed_at".to_string(),
+        ],
+        delimiter,
+    );
+
+    let mut count = 0u64;
+    for r in rows {
+        let row = r.map_err(|e| format!("Failed to collect KB files: {}", e))?;
+        write_tabular_row(
+            &mut out,
+            &[
+                row.path,
+                row.parser,
+                row.source_group,
+                row.chunk_count.to_string(),
+                format_human_bytes(row.size_bytes),
+                row.indexed_at,
+            ],
+            delimiter,
+        );
+        count += 1;
+    }
+
+    fs::write(&path, out).map_err(|e| format!("Failed to write export file: {}", e))?;
+    Ok(count)
+}
 
+fn decode_glossary_manager_output(output: std::process::Output) -> Result<serde_json::Value, String> {
     if !output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -4677,20 +8987,80 @@ fn run_glossary_manager_json(
             .unwrap_or("unknown glossary manager error");
         return Err(format!("glossary manager error: {}", err));
     }
-    let _ = config; // keep parity with other command helpers that load config.
     Ok(parsed)
 }
 
-#[tauri::command]
-fn list_glossary_terms(
-    state: State<'_, AppState>,
+fn run_glossary_manager_json(
+    state: &AppState,
+    args: &[String],
+) -> Result<serde_json::Value, String> {
+    let config = get_config_inner(state)?;
+    let python_bin = find_python_bin(state);
+
+    let mut cmd_args = vec!["-m".to_string(), "scripts.glossary_manager".to_string()];
+    cmd_args.extend_from_slice(args);
+
+    let output = Command::new(&python_bin)
+        .args(&cmd_args)
+        .current_dir(&state.config_path)
+        .output()
+        .map_err(|e| format!("Failed to run glossary manager: {}", e))?;
+
+    let _ = config; // keep parity with other command helpers that load config.
+    decode_glossary_manager_output(output)
+}
+
+/// Like `run_glossary_manager_json`, but for subcommands that take a batch
+/// payload too large/structured for CLI args: the payload is written as
+/// JSON to the child's stdin instead of serialized into `--flag value` pairs.
+fn run_glossary_manager_json_stdin(
+    state: &AppState,
+    args: &[String],
+    payload: &serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    use std::io::Write;
+
+    let config = get_config_inner(state)?;
+    let python_bin = find_python_bin(state);
+
+    let mut cmd_args = vec!["-m".to_string(), "scripts.glossary_manager".to_string()];
+    cmd_args.extend_from_slice(args);
+
+    let mut child = Command::new(&python_bin)
+        .args(&cmd_args)
+        .current_dir(&state.config_path)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run glossary manager: {}", e))?;
+
+    let payload_bytes = serde_json::to_vec(payload)
+        .map_err(|e| format!("Failed to encode glossary batch payload: {}", e))?;
+    child
+        .stdin
+        .as_mut()
+        .ok_or("Failed to open glossary manager stdin")?
+        .write_all(&payload_bytes)
+        .map_err(|e| format!("Failed to write glossary batch payload: {}", e))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run glossary manager: {}", e))?;
+
+    let _ = config; // keep parity with other command helpers that load config.
+    decode_glossary_manager_output(output)
+}
+
+fn list_glossary_terms_inner(
+    state: &AppState,
     company: Option<String>,
     language_pair: Option<String>,
     query: Option<String>,
     limit: Option<u32>,
     offset: Option<u32>,
 ) -> Result<GlossaryTermList, String> {
-    let config = get_config_inner(&state)?;
+    let config = get_config_inner(state)?;
     let mut args: Vec<String> = vec![
         "list".to_string(),
         "--kb-root".to_string(),
@@ -4723,7 +9093,7 @@ fn list_glossary_terms(
         }
     }
 
-    let parsed = run_glossary_manager_json(&state, &args)?;
+    let parsed = run_glossary_manager_json(state, &args)?;
     let result = parsed
         .get("result")
         .cloned()
@@ -4732,6 +9102,83 @@ fn list_glossary_terms(
         .map_err(|e| format!("Failed to decode glossary terms: {}", e))
 }
 
+#[tauri::command]
+fn list_glossary_terms(
+    state: State<'_, AppState>,
+    company: Option<String>,
+    language_pair: Option<String>,
+    query: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<GlossaryTermList, String> {
+    list_glossary_terms_inner(&state, company, language_pair, query, limit, offset)
+}
+
+/// Highest `--limit` the glossary manager is asked for by `export_glossary_terms`
+/// so it returns every matching term instead of one UI page.
+const GLOSSARY_EXPORT_LIMIT: u32 = 1_000_000;
+
+/// Streams every glossary term matching `company`/`language_pair`/`query` to
+/// `path` as CSV or TSV, ignoring the UI page `limit`. Returns the number of
+/// rows written.
+#[tauri::command]
+fn export_glossary_terms(
+    state: State<'_, AppState>,
+    format: String,
+    path: String,
+    company: Option<String>,
+    language_pair: Option<String>,
+    query: Option<String>,
+) -> Result<u64, String> {
+    let delimiter = tabular_delimiter(&format)?;
+    let terms = list_glossary_terms_inner(
+        &state,
+        company,
+        language_pair,
+        query,
+        Some(GLOSSARY_EXPORT_LIMIT),
+        Some(0),
+    )?;
+
+    let mut out = String::new();
+    write_tabular_row(
+        &mut out,
+        &[
+            "company".to_string(),
+            "source_lang".to_string(),
+            "target_lang".to_string(),
+            "language_pair".to_string(),
+            "source_text".to_string(),
+            "target_text".to_string(),
+            "origin".to_string(),
+            "source_path".to_string(),
+            "updated_at".to_string(),
+        ],
+        delimiter,
+    );
+
+    for term in &terms.items {
+        write_tabular_row(
+            &mut out,
+            &[
+                term.company.clone(),
+                term.source_lang.clone(),
+                term.target_lang.clone(),
+                term.language_pair.clone(),
+                term.source_text.clone(),
+                term.target_text.clone(),
+                term.origin.clone(),
+                term.source_path.clone(),
+                term.updated_at.clone().unwrap_or_default(),
+            ],
+            delimiter,
+        );
+    }
+
+    fs::write(&path, out).map_err(|e| format!("Failed to write export file: {}", e))?;
+    Ok(terms.items.len() as u64)
+}
+
 #[tauri::command]
 fn upsert_glossary_term(
     state: State<'_, AppState>,
@@ -4792,6 +9239,52 @@ fn delete_glossary_term(
     Ok(true)
 }
 
+/// Imports a spreadsheet-sized batch of terms in a single glossary-manager
+/// call instead of one subprocess per row. Each item's result is reported
+/// independently so a single malformed or conflicting row doesn't abort the
+/// rest of the import.
+#[tauri::command]
+fn upsert_glossary_batch(
+    state: State<'_, AppState>,
+    items: Vec<GlossaryBatchUpsertItem>,
+) -> Result<Vec<GlossaryBatchItemResult>, String> {
+    let config = get_config_inner(&state)?;
+    let args: Vec<String> = vec![
+        "batch-upsert".to_string(),
+        "--kb-root".to_string(),
+        config.kb_root,
+    ];
+    let payload = serde_json::json!({ "items": items });
+    let parsed = run_glossary_manager_json_stdin(&state, &args, &payload)?;
+    let result = parsed
+        .get("result")
+        .cloned()
+        .ok_or("glossary manager returned no result")?;
+    serde_json::from_value::<Vec<GlossaryBatchItemResult>>(result)
+        .map_err(|e| format!("Failed to decode glossary batch result: {}", e))
+}
+
+#[tauri::command]
+fn delete_glossary_batch(
+    state: State<'_, AppState>,
+    items: Vec<GlossaryBatchDeleteItem>,
+) -> Result<Vec<GlossaryBatchItemResult>, String> {
+    let config = get_config_inner(&state)?;
+    let args: Vec<String> = vec![
+        "batch-delete".to_string(),
+        "--kb-root".to_string(),
+        config.kb_root,
+    ];
+    let payload = serde_json::json!({ "items": items });
+    let parsed = run_glossary_manager_json_stdin(&state, &args, &payload)?;
+    let result = parsed
+        .get("result")
+        .cloned()
+        .ok_or("glossary manager returned no result")?;
+    serde_json::from_value::<Vec<GlossaryBatchItemResult>>(result)
+        .map_err(|e| format!("Failed to decode glossary batch result: {}", e))
+}
+
 #[tauri::command]
 fn lookup_glossary_text(
     state: State<'_, AppState>,
@@ -4830,137 +9323,287 @@ fn lookup_glossary_text(
 // Docker / ClawRAG Commands
 // ============================================================================
 
-const CLAWRAG_CONTAINERS: &[&str] = &[
-    "clawrag-gateway",
-    "clawrag-backend",
-    "clawrag-chromadb",
-    "clawrag-ollama",
+/// A ClawRAG container's compose definition: what `compose_up` creates if
+/// Docker reports it `not_found`, rather than only starting a pre-existing
+/// container.
+struct ClawragContainerSpec {
+    name: &'static str,
+    image: &'static str,
+    /// `host:container` bind mounts, bollard `HostConfig::binds` format.
+    volumes: &'static [&'static str],
+}
+
+const CLAWRAG_COMPOSE: &[ClawragContainerSpec] = &[
+    ClawragContainerSpec {
+        name: "clawrag-gateway",
+        image: "clawrag/gateway:latest",
+        volumes: &[],
+    },
+    ClawragContainerSpec {
+        name: "clawrag-backend",
+        image: "clawrag/backend:latest",
+        volumes: &["clawrag-backend-data:/data"],
+    },
+    ClawragContainerSpec {
+        name: "clawrag-chromadb",
+        image: "chromadb/chroma:latest",
+        volumes: &["clawrag-chromadb-data:/chroma/chroma"],
+    },
+    ClawragContainerSpec {
+        name: "clawrag-ollama",
+        image: "ollama/ollama:latest",
+        volumes: &["clawrag-ollama-data:/root/.ollama"],
+    },
 ];
 
+/// How long `compose_up` polls `inspect_container` for a fresh container to
+/// report healthy (or just running, if it has no healthcheck) before giving
+/// up and returning whatever state was last observed.
+const DOCKER_HEALTH_TIMEOUT_SECS: u64 = 30;
+const DOCKER_HEALTH_POLL_INTERVAL_MS: u64 = 500;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerContainer {
     pub name: String,
-    pub status: String, // "running" | "stopped" | "not_found"
+    pub status: String, // "healthy" | "unhealthy" | "starting" | "running" | "stopped" | "not_found" | "unknown"
     pub image: String,
 }
 
-const DOCKER_PATHS: &[&str] = &[
-    "/usr/local/bin/docker",
-    "/opt/homebrew/bin/docker",
-    "/usr/bin/docker",
-];
-
-fn find_docker() -> Option<String> {
-    for path in DOCKER_PATHS {
-        if std::path::Path::new(path).exists() {
-            return Some(path.to_string());
-        }
-    }
-    None
-}
-
-fn docker_available() -> bool {
-    if let Some(docker) = find_docker() {
-        Command::new(&docker)
-            .arg("info")
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status()
-            .map(|s| s.success())
-            .unwrap_or(false)
-    } else {
-        false
-    }
+fn docker_client() -> Result<bollard::Docker, String> {
+    bollard::Docker::connect_with_socket_defaults()
+        .map_err(|e| format!("Failed to connect to Docker: {}", e))
 }
 
-fn docker_cmd(args: &[&str]) -> Result<std::process::Output, String> {
-    let docker = find_docker().ok_or("Docker binary not found")?;
-    Command::new(&docker)
-        .args(args)
-        .output()
-        .map_err(|e| format!("Failed to run docker: {}", e))
-}
-
-fn parse_docker_containers(stdout: &str) -> Vec<DockerContainer> {
-    let mut containers: Vec<DockerContainer> = Vec::new();
-    for name in CLAWRAG_CONTAINERS {
-        let mut found = false;
-        for line in stdout.lines() {
-            let parts: Vec<&str> = line.split('\t').collect();
-            if parts.len() >= 3 && parts[0] == *name {
-                let status = if parts[1].starts_with("Up") {
-                    "running"
+/// Inspects a single container and maps `State.Health.Status` (or, absent a
+/// healthcheck, `State.Running`) onto `DockerContainer`, instead of
+/// string-matching `docker ps` output.
+async fn docker_container_health(docker: &bollard::Docker, name: &str) -> DockerContainer {
+    match docker
+        .inspect_container(name, None::<bollard::container::InspectContainerOptions>)
+        .await
+    {
+        Ok(details) => {
+            let state = details.state.unwrap_or_default();
+            let running = state.running.unwrap_or(false);
+            let health_status = state
+                .health
+                .as_ref()
+                .and_then(|h| h.status)
+                .map(|s| format!("{:?}", s).to_lowercase());
+            let status = health_status.unwrap_or_else(|| {
+                if running {
+                    "running".to_string()
                 } else {
-                    "stopped"
-                };
-                containers.push(DockerContainer {
-                    name: name.to_string(),
-                    status: status.to_string(),
-                    image: parts[2].to_string(),
-                });
-                found = true;
-                break;
+                    "stopped".to_string()
+                }
+            });
+            let image = details
+                .config
+                .and_then(|c| c.image)
+                .unwrap_or_else(|| name.to_string());
+            DockerContainer {
+                name: name.to_string(),
+                status,
+                image,
             }
         }
-        if !found {
-            containers.push(DockerContainer {
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        }) => DockerContainer {
+            name: name.to_string(),
+            status: "not_found".to_string(),
+            image: String::new(),
+        },
+        Err(e) => {
+            eprintln!("[docker] failed to inspect {}: {}", name, e);
+            DockerContainer {
                 name: name.to_string(),
-                status: "not_found".to_string(),
+                status: "unknown".to_string(),
                 image: String::new(),
-            });
+            }
         }
     }
-    containers
 }
 
-#[tauri::command]
-fn get_docker_status() -> Result<Vec<DockerContainer>, String> {
-    if !docker_available() {
-        return Err("Docker is not running".to_string());
+async fn create_clawrag_container(
+    docker: &bollard::Docker,
+    spec: &ClawragContainerSpec,
+) -> Result<(), String> {
+    let host_config = bollard::models::HostConfig {
+        binds: Some(spec.volumes.iter().map(|v| v.to_string()).collect()),
+        ..Default::default()
+    };
+    let config = bollard::container::Config {
+        image: Some(spec.image.to_string()),
+        host_config: Some(host_config),
+        ..Default::default()
+    };
+    docker
+        .create_container(
+            Some(bollard::container::CreateContainerOptions {
+                name: spec.name.to_string(),
+                platform: None,
+            }),
+            config,
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Failed to create {}: {}", spec.name, e))
+}
+
+/// Polls `inspect_container` until `name` reports healthy (or running, for
+/// containers without a healthcheck) or `DOCKER_HEALTH_TIMEOUT_SECS`
+/// elapses, replacing a fixed `sleep(3)` with an actual readiness check.
+async fn wait_for_container_healthy(docker: &bollard::Docker, name: &str) -> DockerContainer {
+    let deadline =
+        std::time::Instant::now() + std::time::Duration::from_secs(DOCKER_HEALTH_TIMEOUT_SECS);
+    loop {
+        let container = docker_container_health(docker, name).await;
+        if matches!(container.status.as_str(), "healthy" | "running") {
+            return container;
+        }
+        if std::time::Instant::now() >= deadline {
+            return container;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(
+            DOCKER_HEALTH_POLL_INTERVAL_MS,
+        ))
+        .await;
     }
+}
 
-    let output = docker_cmd(&[
-        "ps",
-        "-a",
-        "--format",
-        "{{.Names}}\t{{.Status}}\t{{.Image}}",
-    ])?;
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_docker_containers(&stdout))
+#[tauri::command]
+async fn get_docker_status() -> Result<Vec<DockerContainer>, String> {
+    let docker = docker_client()?;
+    let mut containers = Vec::new();
+    for spec in CLAWRAG_COMPOSE {
+        containers.push(docker_container_health(&docker, spec.name).await);
+    }
+    Ok(containers)
 }
 
 #[tauri::command]
 async fn start_docker_services() -> Result<Vec<DockerContainer>, String> {
-    if !docker_available() {
-        return Err("Docker is not running. Please start Docker Desktop first.".to_string());
+    let docker = docker_client()?;
+    let mut containers = Vec::new();
+    for spec in CLAWRAG_COMPOSE {
+        let _ = docker
+            .start_container(spec.name, None::<bollard::container::StartContainerOptions<String>>)
+            .await;
+        containers.push(wait_for_container_healthy(&docker, spec.name).await);
     }
+    Ok(containers)
+}
 
-    for name in CLAWRAG_CONTAINERS {
-        let _ = docker_cmd(&["start", name]);
+#[tauri::command]
+async fn stop_docker_services() -> Result<(), String> {
+    let docker = docker_client()?;
+    for spec in CLAWRAG_COMPOSE {
+        let _ = docker
+            .stop_container(spec.name, None::<bollard::container::StopContainerOptions>)
+            .await;
     }
+    Ok(())
+}
 
-    tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+/// Brings every ClawRAG container up, creating it from `CLAWRAG_COMPOSE`
+/// first if Docker reports it `not_found` rather than only starting
+/// pre-existing containers.
+#[tauri::command]
+async fn compose_up() -> Result<Vec<DockerContainer>, String> {
+    let docker = docker_client()?;
+    let mut containers = Vec::new();
+    for spec in CLAWRAG_COMPOSE {
+        let existing = docker_container_health(&docker, spec.name).await;
+        if existing.status == "not_found" {
+            create_clawrag_container(&docker, spec).await?;
+        }
+        docker
+            .start_container(spec.name, None::<bollard::container::StartContainerOptions<String>>)
+            .await
+            .map_err(|e| format!("Failed to start {}: {}", spec.name, e))?;
+        containers.push(wait_for_container_healthy(&docker, spec.name).await);
+    }
+    Ok(containers)
+}
 
-    let output = docker_cmd(&[
-        "ps",
-        "-a",
-        "--format",
-        "{{.Names}}\t{{.Status}}\t{{.Image}}",
-    ])?;
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_docker_containers(&stdout))
+#[tauri::command]
+async fn compose_down() -> Result<(), String> {
+    let docker = docker_client()?;
+    for spec in CLAWRAG_COMPOSE {
+        let _ = docker
+            .stop_container(spec.name, None::<bollard::container::StopContainerOptions>)
+            .await;
+    }
+    Ok(())
 }
 
+/// Tails `name`'s stdout/stderr via the Docker Engine API and emits each
+/// line as a `container-log-line` event, turning the Docker panel from
+/// status-only into something that can show why a container failed to come
+/// up. With `follow` set, keeps emitting until the stream ends or
+/// `stop_container_log_stream` flips this container's stop flag.
 #[tauri::command]
-async fn stop_docker_services() -> Result<(), String> {
-    if !docker_available() {
-        return Err("Docker is not running".to_string());
+async fn stream_container_logs(
+    name: String,
+    tail: u32,
+    follow: bool,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    use futures_util::StreamExt;
+
+    if !CLAWRAG_COMPOSE.iter().any(|spec| spec.name == name) {
+        return Err(format!("Unknown container: {}", name));
     }
 
-    for name in CLAWRAG_CONTAINERS {
-        let _ = docker_cmd(&["stop", name]);
+    let stop_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    state
+        .docker_log_streams
+        .lock()
+        .unwrap()
+        .insert(name.clone(), stop_flag.clone());
+
+    let docker = docker_client()?;
+    let options = bollard::container::LogsOptions::<String> {
+        stdout: true,
+        stderr: true,
+        tail: tail.to_string(),
+        follow,
+        ..Default::default()
+    };
+    let mut stream = docker.logs(&name, Some(options));
+
+    while let Some(chunk) = stream.next().await {
+        if stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
+            break;
+        }
+        match chunk {
+            Ok(output) => {
+                let line = output.to_string();
+                let _ = app.emit(
+                    "container-log-line",
+                    serde_json::json!({ "name": name, "line": line.trim_end() }),
+                );
+            }
+            Err(e) => {
+                eprintln!("[docker] log stream error for {}: {}", name, e);
+                break;
+            }
+        }
     }
 
+    state.docker_log_streams.lock().unwrap().remove(&name);
+    Ok(())
+}
+
+/// Ends an in-flight `stream_container_logs` follow for `name`, if one is
+/// running.
+#[tauri::command]
+fn stop_container_log_stream(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(flag) = state.docker_log_streams.lock().unwrap().get(&name) {
+        flag.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
     Ok(())
 }
 
@@ -5049,6 +9692,157 @@ fn write_auth_profiles(profiles: &serde_json::Value) -> Result<(), String> {
     fs::write(&path, content).map_err(|e| format!("Failed to write auth profiles: {}", e))
 }
 
+// ============================================================================
+// Secret Encryption
+// ============================================================================
+
+const SECRET_ENC_PREFIX: &str = "enc:v1:";
+const SECRET_KEYRING_SERVICE: &str = "openclaw";
+const SECRET_KEYRING_USER: &str = "master-key";
+/// Profile fields that hold sensitive material and should be stored encrypted.
+const SECRET_FIELDS: &[&str] = &["key", "access", "refresh"];
+
+/// Decode a base64-encoded 32-byte master key, used for both the keychain
+/// value and the `OPENCLAW_SECRET_KEY` fallback.
+fn decode_master_key(raw: &str) -> Result<[u8; 32], String> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(raw.trim())
+        .map_err(|e| format!("Invalid master key encoding: {}", e))?;
+    bytes
+        .try_into()
+        .map_err(|_| "Master key must be exactly 32 bytes".to_string())
+}
+
+/// Load the AEAD master key from the OS keychain, provisioning a fresh
+/// random one on first use so `set_api_key` works out of the box on a
+/// clean install instead of requiring an operator to pre-seed the
+/// keychain or export `OPENCLAW_SECRET_KEY` before ever touching a
+/// profile. Falls back to `OPENCLAW_SECRET_KEY` if the keychain itself is
+/// unavailable (e.g. a headless box with no keychain backend).
+fn load_master_key() -> Result<[u8; 32], String> {
+    if let Ok(entry) = keyring::Entry::new(SECRET_KEYRING_SERVICE, SECRET_KEYRING_USER) {
+        if let Ok(secret) = entry.get_password() {
+            return decode_master_key(&secret);
+        }
+        if let Ok(key_bytes) = provision_master_key(&entry) {
+            return Ok(key_bytes);
+        }
+    }
+    if let Ok(env_key) = std::env::var("OPENCLAW_SECRET_KEY") {
+        return decode_master_key(&env_key);
+    }
+    Err("No master key available in OS keychain or OPENCLAW_SECRET_KEY".to_string())
+}
+
+/// Generates a random 32-byte key and persists it into `entry` so every
+/// later `load_master_key` call on this machine reuses the same key.
+fn provision_master_key(entry: &keyring::Entry) -> Result<[u8; 32], String> {
+    use base64::Engine;
+    use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+
+    let mut key_bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut key_bytes);
+    let encoded = base64::engine::general_purpose::STANDARD.encode(key_bytes);
+    entry
+        .set_password(&encoded)
+        .map_err(|e| format!("Failed to persist generated master key: {}", e))?;
+    Ok(key_bytes)
+}
+
+fn is_encrypted_secret(value: &str) -> bool {
+    value.starts_with(SECRET_ENC_PREFIX)
+}
+
+/// Encrypt a secret for storage, producing a tagged `enc:v1:<base64>` string
+/// containing a fresh random nonce followed by the ciphertext.
+fn encrypt_secret(plaintext: &str) -> Result<String, String> {
+    use base64::Engine;
+    use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+    use chacha20poly1305::XChaCha20Poly1305;
+
+    let key_bytes = load_master_key()?;
+    let cipher = XChaCha20Poly1305::new_from_slice(&key_bytes)
+        .map_err(|e| format!("Failed to initialize cipher: {}", e))?;
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|e| format!("Encryption failed: {}", e))?;
+
+    let mut combined = nonce.to_vec();
+    combined.extend_from_slice(&ciphertext);
+    Ok(format!(
+        "{}{}",
+        SECRET_ENC_PREFIX,
+        base64::engine::general_purpose::STANDARD.encode(combined)
+    ))
+}
+
+/// Decrypt a stored secret. Legacy plaintext values (no `enc:v1:` prefix)
+/// are passed through unchanged so existing profiles keep working; a
+/// malformed or undecryptable tagged value is reported as an error so
+/// callers can surface it as a corrupt secret rather than a missing one.
+fn decrypt_secret(stored: &str) -> Result<String, String> {
+    use base64::Engine;
+    use chacha20poly1305::aead::{Aead, KeyInit};
+    use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+    let Some(encoded) = stored.strip_prefix(SECRET_ENC_PREFIX) else {
+        return Ok(stored.to_string());
+    };
+
+    let combined = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|_| "Corrupt secret: invalid base64 payload".to_string())?;
+    if combined.len() < 24 {
+        return Err("Corrupt secret: truncated payload".to_string());
+    }
+    let (nonce_bytes, ciphertext) = combined.split_at(24);
+    let key_bytes = load_master_key()?;
+    let cipher = XChaCha20Poly1305::new_from_slice(&key_bytes)
+        .map_err(|e| format!("Failed to initialize cipher: {}", e))?;
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| "Corrupt secret: decryption failed".to_string())?;
+    String::from_utf8(plaintext).map_err(|_| "Corrupt secret: invalid utf-8".to_string())
+}
+
+/// Load auth profiles, lazily re-encrypting any legacy plaintext secrets the
+/// first time they're encountered and persisting the migrated profiles back
+/// to disk.
+fn load_auth_profiles_migrated() -> Result<serde_json::Value, String> {
+    let mut profiles = read_auth_profiles()?;
+    let mut changed = false;
+
+    if let Some(profiles_obj) = profiles.get_mut("profiles").and_then(|p| p.as_object_mut()) {
+        for profile in profiles_obj.values_mut() {
+            let Some(profile_obj) = profile.as_object_mut() else {
+                continue;
+            };
+            for field in SECRET_FIELDS {
+                let raw = profile_obj.get(*field).and_then(|v| v.as_str());
+                if let Some(raw) = raw {
+                    if !is_encrypted_secret(raw) {
+                        if let Ok(encrypted) = encrypt_secret(raw) {
+                            profile_obj.insert(
+                                (*field).to_string(),
+                                serde_json::Value::String(encrypted),
+                            );
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if changed {
+        write_auth_profiles(&profiles)?;
+    }
+
+    Ok(profiles)
+}
+
 /// Known provider definitions
 fn get_known_providers() -> Vec<(&'static str, &'static str, &'static str)> {
     vec![
@@ -5060,44 +9854,191 @@ fn get_known_providers() -> Vec<(&'static str, &'static str, &'static str)> {
     ]
 }
 
-#[tauri::command]
-fn get_api_providers() -> Result<Vec<ApiProvider>, String> {
-    let profiles = read_auth_profiles()?;
+// ============================================================================
+// OAuth Token Refresh
+// ============================================================================
+
+/// How far ahead of `expires` we proactively refresh, so a token doesn't
+/// die mid-request.
+const OAUTH_REFRESH_SKEW_MS: i64 = 5 * 60 * 1000;
+
+struct OauthProviderConfig {
+    token_endpoint: &'static str,
+    client_id: &'static str,
+}
+
+/// Token endpoint / client ID for each OAuth provider in `get_known_providers`.
+fn oauth_provider_config(provider_id: &str) -> Option<OauthProviderConfig> {
+    match provider_id {
+        "openai-codex" => Some(OauthProviderConfig {
+            token_endpoint: "https://auth.openai.com/oauth/token",
+            client_id: "app_EMoamEEZ73f0CkXaXp7hrann",
+        }),
+        "google-antigravity" => Some(OauthProviderConfig {
+            token_endpoint: "https://oauth2.googleapis.com/token",
+            client_id: "antigravity-proxy-client",
+        }),
+        _ => None,
+    }
+}
+
+fn oauth_needs_refresh(profile: &serde_json::Value, now: i64) -> bool {
+    match profile.get("expires").and_then(|e| e.as_i64()) {
+        Some(expires) => expires < now + OAUTH_REFRESH_SKEW_MS,
+        None => false,
+    }
+}
+
+/// Get (creating if needed) the per-provider lock used to serialize
+/// concurrent refreshes of the same profile.
+fn oauth_refresh_lock(state: &AppState, provider: &str) -> std::sync::Arc<tokio::sync::Mutex<()>> {
+    let mut locks = state.oauth_refresh_locks.lock().unwrap();
+    locks
+        .entry(provider.to_string())
+        .or_insert_with(|| std::sync::Arc::new(tokio::sync::Mutex::new(())))
+        .clone()
+}
+
+/// Refresh an OAuth provider's access token using its stored refresh token.
+/// Serialized per-provider so two concurrent callers can't both spend the
+/// same refresh token; on HTTP/parse failure the profile is left untouched
+/// and stays `expired`.
+async fn refresh_oauth_token_inner(state: &AppState, provider: &str) -> Result<(), String> {
+    let config = oauth_provider_config(provider)
+        .ok_or_else(|| format!("No OAuth refresh config for provider '{}'", provider))?;
+
+    let lock = oauth_refresh_lock(state, provider);
+    let _guard = lock.lock().await;
+
+    // Re-read after acquiring the lock in case another caller just refreshed
+    // this profile while we were waiting.
+    let mut profiles = read_auth_profiles()?;
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis() as i64;
+    let (profile_key, profile) = find_active_profile(&profiles, provider, now)
+        .map(|(key, p)| (key.to_string(), p.clone()))
+        .ok_or_else(|| format!("No profile found for provider '{}'", provider))?;
 
-    // Helper to find the active profile for a provider
-    fn find_active_profile<'a>(
-        profiles: &'a serde_json::Value,
-        provider_id: &str,
-    ) -> Option<(&'a str, &'a serde_json::Value)> {
-        // First check lastGood for the active profile key
-        if let Some(last_good) = profiles.get("lastGood").and_then(|lg| lg.get(provider_id)) {
-            if let Some(key) = last_good.as_str() {
-                if let Some(profile) = profiles.get("profiles").and_then(|p| p.get(key)) {
-                    return Some((key, profile));
-                }
+    if !oauth_needs_refresh(&profile, now) {
+        return Ok(());
+    }
+
+    let refresh_raw = profile
+        .get("refresh")
+        .and_then(|r| r.as_str())
+        .ok_or_else(|| "Profile has no refresh token".to_string())?;
+    let refresh_token = decrypt_secret(refresh_raw)?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(config.token_endpoint)
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token.as_str()),
+            ("client_id", config.client_id),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Token refresh request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Token refresh failed with status {}",
+            response.status()
+        ));
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse refresh response: {}", e))?;
+
+    let access_token = body
+        .get("access_token")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Refresh response missing access_token".to_string())?;
+    let expires_in = body.get("expires_in").and_then(|v| v.as_i64()).unwrap_or(0);
+    // Honor refresh-token rotation: only replace the stored refresh token
+    // when the response actually includes a new one.
+    let rotated_refresh = body.get("refresh_token").and_then(|v| v.as_str());
+
+    let mut updated = profile.clone();
+    updated["access"] = serde_json::Value::String(encrypt_secret(access_token)?);
+    if let Some(new_refresh) = rotated_refresh {
+        updated["refresh"] = serde_json::Value::String(encrypt_secret(new_refresh)?);
+    }
+    updated["expires"] = serde_json::Value::from(now + expires_in * 1000);
+
+    if let Some(profiles_obj) = profiles.get_mut("profiles").and_then(|p| p.as_object_mut()) {
+        profiles_obj.insert(profile_key, updated);
+    }
+    write_auth_profiles(&profiles)
+}
+
+#[tauri::command]
+async fn refresh_oauth_token(provider: String, state: State<'_, AppState>) -> Result<(), String> {
+    refresh_oauth_token_inner(&state, &provider).await
+}
+
+/// Find the active profile for a provider: prefer the `lastGood` pointer,
+/// falling back to the newest non-expired profile keyed `"{provider_id}:*"`
+/// (an expired one is returned only if nothing unexpired exists, so callers
+/// still have a profile to report as expired rather than missing).
+fn find_active_profile<'a>(
+    profiles: &'a serde_json::Value,
+    provider_id: &str,
+    now: i64,
+) -> Option<(&'a str, &'a serde_json::Value)> {
+    if let Some(last_good) = profiles.get("lastGood").and_then(|lg| lg.get(provider_id)) {
+        if let Some(key) = last_good.as_str() {
+            if let Some(profile) = profiles.get("profiles").and_then(|p| p.get(key)) {
+                return Some((key, profile));
             }
         }
+    }
 
-        // Fallback: search for any profile matching this provider
-        if let Some(profiles_obj) = profiles.get("profiles").and_then(|p| p.as_object()) {
-            for (key, profile) in profiles_obj {
-                if key.starts_with(&format!("{}:", provider_id)) {
-                    return Some((key, profile));
+    if let Some(profiles_obj) = profiles.get("profiles").and_then(|p| p.as_object()) {
+        let prefix = format!("{}:", provider_id);
+        let mut best: Option<(&str, &serde_json::Value, i64, bool)> = None;
+        for (key, profile) in profiles_obj {
+            if !key.starts_with(&prefix) {
+                continue;
+            }
+            let created_at = profile.get("created_at").and_then(|c| c.as_i64()).unwrap_or(0);
+            let expired = profile
+                .get("expires_at")
+                .or_else(|| profile.get("expires"))
+                .and_then(|e| e.as_i64())
+                .map(|exp| exp < now)
+                .unwrap_or(false);
+            let is_better = match best {
+                None => true,
+                Some((_, _, best_created, best_expired)) => {
+                    (best_expired && !expired) || (expired == best_expired && created_at > best_created)
                 }
+            };
+            if is_better {
+                best = Some((key, profile, created_at, expired));
             }
         }
-
-        None
+        if let Some((key, profile, _, _)) = best {
+            return Some((key, profile));
+        }
     }
 
-    let providers: Vec<ApiProvider> = get_known_providers()
+    None
+}
+
+/// Build the `ApiProvider` status list from already-loaded profiles. Pure
+/// (no refresh side effects), so it's safe to call on a hot path like a
+/// metrics scrape as well as from `get_api_providers`.
+fn compute_api_providers(profiles: &serde_json::Value, now: i64) -> Vec<ApiProvider> {
+    get_known_providers()
         .into_iter()
         .map(|(id, name, auth_type)| {
-            let profile_data = find_active_profile(&profiles, id);
+            let profile_data = find_active_profile(&profiles, id, now);
             let (_profile_key, profile) = match profile_data {
                 Some((key, p)) => (Some(key), Some(p)),
                 None => (None, None),
@@ -5116,80 +10057,265 @@ fn get_api_providers() -> Result<Vec<ApiProvider>, String> {
                             let expires = p.get("expires").and_then(|e| e.as_i64());
                             let provider_type = p.get("type").and_then(|t| t.as_str());
 
-                            // Validate OAuth profile structure
-                            let is_valid_oauth = provider_type == Some("oauth")
-                                && access_token.is_some()
-                                && refresh_token.is_some();
+                            // Decrypt transparently; a decrypt failure means the
+                            // secret is tampered/corrupt rather than absent.
+                            let decrypt_ok = access_token
+                                .map(decrypt_secret)
+                                .transpose()
+                                .and(refresh_token.map(decrypt_secret).transpose());
 
-                            if !is_valid_oauth {
-                                (false, email_val, expires, "missing".to_string())
-                            } else if let Some(exp) = expires {
-                                if exp < now {
-                                    (true, email_val, Some(exp), "expired".to_string())
+                            if decrypt_ok.is_err() {
+                                (false, email_val, expires, ApiProviderStatus::Corrupt)
+                            } else {
+                                // Validate OAuth profile structure
+                                let is_valid_oauth = provider_type == Some("oauth")
+                                    && access_token.is_some()
+                                    && refresh_token.is_some();
+
+                                if !is_valid_oauth {
+                                    (false, email_val, expires, ApiProviderStatus::Missing)
+                                } else if let Some(exp) = expires {
+                                    if exp < now {
+                                        (true, email_val, Some(exp), ApiProviderStatus::Expired)
+                                    } else {
+                                        (true, email_val, Some(exp), ApiProviderStatus::Configured)
+                                    }
                                 } else {
-                                    (true, email_val, Some(exp), "configured".to_string())
+                                    (true, email_val, None, ApiProviderStatus::Configured)
                                 }
-                            } else {
-                                (true, email_val, None, "configured".to_string())
                             }
                         }
-                        None => (false, None, None, "missing".to_string()),
+                        None => (false, None, None, ApiProviderStatus::Missing),
                     }
                 }
                 "api_key" => {
-                    // For API key providers, check for key presence
+                    // For API key providers, check for key presence and,
+                    // now that profiles carry metadata, expiry.
                     match profile {
                         Some(p) => {
                             let key = p.get("key").and_then(|k| k.as_str());
                             let provider_type = p.get("type").and_then(|t| t.as_str());
-                            let is_valid = provider_type == Some("api_key") && key.is_some();
-                            (
-                                is_valid,
-                                None,
-                                None,
-                                if is_valid { "configured" } else { "missing" }.to_string(),
-                            )
+                            let expires = p.get("expires_at").and_then(|e| e.as_i64());
+                            match key.map(decrypt_secret) {
+                                Some(Err(_)) => {
+                                    (false, None, expires, ApiProviderStatus::Corrupt)
+                                }
+                                _ => {
+                                    let is_valid =
+                                        provider_type == Some("api_key") && key.is_some();
+                                    let status = if !is_valid {
+                                        ApiProviderStatus::Missing
+                                    } else if expires.map(|exp| exp < now).unwrap_or(false) {
+                                        ApiProviderStatus::Expired
+                                    } else {
+                                        ApiProviderStatus::Configured
+                                    };
+                                    (is_valid, None, expires, status)
+                                }
+                            }
                         }
-                        None => (false, None, None, "missing".to_string()),
+                        None => (false, None, None, ApiProviderStatus::Missing),
                     }
                 }
-                _ => (false, None, None, "missing".to_string()),
+                _ => (false, None, None, ApiProviderStatus::Missing),
+            };
+
+            let auth_type = match auth_type {
+                "oauth" => ApiAuthType::Oauth,
+                "api_key" => ApiAuthType::ApiKey,
+                "none" => ApiAuthType::NoAuth,
+                _ => ApiAuthType::Unknown,
             };
 
             ApiProvider {
                 id: id.to_string(),
                 name: name.to_string(),
-                auth_type: auth_type.to_string(),
+                auth_type,
                 status,
                 has_key,
                 email,
                 expires_at,
             }
         })
-        .collect();
+        .collect()
+}
+
+#[tauri::command]
+async fn get_api_providers(state: State<'_, AppState>) -> Result<Vec<ApiProvider>, String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+
+    // Opportunistically refresh OAuth profiles that are expired or within
+    // the refresh skew window before building the status list.
+    for (id, _name, auth_type) in get_known_providers() {
+        if auth_type != "oauth" {
+            continue;
+        }
+        let profiles = load_auth_profiles_migrated()?;
+        let needs_refresh = find_active_profile(&profiles, id, now)
+            .map(|(_key, p)| oauth_needs_refresh(p, now))
+            .unwrap_or(false);
+        if needs_refresh {
+            let _ = refresh_oauth_token_inner(&state, id).await;
+        }
+    }
+
+    let profiles = load_auth_profiles_migrated()?;
+    Ok(compute_api_providers(&profiles, now))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityEventKind {
+    Call,
+    Error,
+    RateLimit,
+}
+
+/// A single provider-call event, appended as a JSON line to `activity.ndjson`
+/// at the point a provider is actually invoked. This is the structured
+/// replacement for guessing activity from `worker.log` keyword matches.
+///
+/// The actual provider calls happen inside `scripts.openclaw_v4_dispatcher`,
+/// the out-of-tree Python process `spawn_dispatcher` shells out to -- there
+/// is no in-tree Rust call site that invokes a provider directly, so
+/// `record_activity_event` below can only be called from Rust code paths
+/// that make their own provider requests (e.g. `refresh_oauth_token_inner`'s
+/// token exchange, `get_api_usage_inner`'s usage fetch). Until the Python
+/// dispatcher is updated to append matching lines for the calls it makes,
+/// `read_structured_activity` will keep returning `None` for dispatcher-
+/// driven activity and `estimate_provider_activity` will keep falling back
+/// to `estimate_provider_activity_from_keywords` for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    pub ts: i64,
+    pub provider: String,
+    pub model: Option<String>,
+    pub kind: ActivityEventKind,
+    pub latency_ms: Option<u64>,
+    pub status_code: Option<u16>,
+}
+
+fn activity_log_path(state: &AppState) -> String {
+    format!("{}/activity.ndjson", state.logs_dir)
+}
 
-    Ok(providers)
+/// Appends one `ActivityEvent` to `activity.ndjson` as a single JSON line.
+/// Best-effort: a write failure (missing `logs_dir`, full disk, ...) is
+/// swallowed rather than propagated, since activity logging must never be
+/// the reason a provider call itself fails.
+fn record_activity_event(
+    state: &AppState,
+    provider: &str,
+    model: Option<String>,
+    kind: ActivityEventKind,
+    latency_ms: Option<u64>,
+    status_code: Option<u16>,
+) {
+    let event = ActivityEvent {
+        ts: now_epoch_ms(),
+        provider: provider.to_string(),
+        model,
+        kind,
+        latency_ms,
+        status_code,
+    };
+    let Ok(line) = serde_json::to_string(&event) else {
+        return;
+    };
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(activity_log_path(state))
+    {
+        use std::io::Write;
+        let _ = writeln!(file, "{}", line);
+    }
 }
 
-/// Provider activity estimation from local logs
+/// Provider activity estimation, either from the structured activity log
+/// (exact) or the legacy keyword parser (a guess, `confidence: Low`).
 struct ProviderActivity {
     calls: u64,
     errors: u64,
+    rate_limited: u64,
     last_seen_at: Option<i64>,
+    p50_latency_ms: Option<u64>,
+    p95_latency_ms: Option<u64>,
+    confidence: ApiUsageConfidence,
 }
 
-/// Estimate provider activity by parsing worker.log and telegram.log
-fn estimate_provider_activity(
+fn latency_percentile(sorted_latencies: &[u64], p: f64) -> Option<u64> {
+    if sorted_latencies.is_empty() {
+        return None;
+    }
+    let idx = ((sorted_latencies.len() as f64 - 1.0) * p).round() as usize;
+    sorted_latencies.get(idx).copied()
+}
+
+/// Aggregate exact counts/latencies for `provider` from `activity.ndjson`,
+/// tailing only events within `cutoff`. Returns `None` if the structured
+/// log doesn't exist yet, so the caller can fall back to the keyword parser.
+fn read_structured_activity(state: &AppState, provider: &str, cutoff: i64) -> Option<ProviderActivity> {
+    let content = std::fs::read_to_string(activity_log_path(state)).ok()?;
+
+    let mut calls: u64 = 0;
+    let mut errors: u64 = 0;
+    let mut rate_limited: u64 = 0;
+    let mut last_seen_at: Option<i64> = None;
+    let mut latencies: Vec<u64> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Ok(event) = serde_json::from_str::<ActivityEvent>(line) else {
+            continue;
+        };
+        if event.provider != provider || event.ts < cutoff {
+            continue;
+        }
+
+        calls += 1;
+        match event.kind {
+            ActivityEventKind::Call => {}
+            ActivityEventKind::Error => errors += 1,
+            ActivityEventKind::RateLimit => {
+                errors += 1;
+                rate_limited += 1;
+            }
+        }
+        if let Some(latency_ms) = event.latency_ms {
+            latencies.push(latency_ms);
+        }
+        last_seen_at = Some(last_seen_at.unwrap_or(0).max(event.ts));
+    }
+
+    latencies.sort_unstable();
+    Some(ProviderActivity {
+        calls,
+        errors,
+        rate_limited,
+        last_seen_at,
+        p50_latency_ms: latency_percentile(&latencies, 0.50),
+        p95_latency_ms: latency_percentile(&latencies, 0.95),
+        confidence: ApiUsageConfidence::High,
+    })
+}
+
+/// Legacy fallback: estimate provider activity by matching keywords against
+/// `worker.log`/`telegram.log`. Conflates providers whose names share
+/// substrings and can't distinguish real errors from incidental mentions of
+/// "error", so it's only used when `activity.ndjson` doesn't exist yet and
+/// is always reported at `confidence: Low`.
+fn estimate_provider_activity_from_keywords(
     state: &AppState,
     provider: &str,
-    range_hours: u64,
+    cutoff: i64,
 ) -> ProviderActivity {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64;
-    let cutoff = now - (range_hours as i64 * 3600 * 1000);
-
     // Provider keywords to match in log lines
     let (provider_keywords, error_keywords) = match provider {
         "moonshot" => (
@@ -5212,7 +10338,11 @@ fn estimate_provider_activity(
             return ProviderActivity {
                 calls: 0,
                 errors: 0,
+                rate_limited: 0,
                 last_seen_at: None,
+                p50_latency_ms: None,
+                p95_latency_ms: None,
+                confidence: ApiUsageConfidence::Low,
             }
         }
     };
@@ -5314,166 +10444,476 @@ fn estimate_provider_activity(
     ProviderActivity {
         calls,
         errors,
+        rate_limited: 0,
         last_seen_at,
+        p50_latency_ms: None,
+        p95_latency_ms: None,
+        confidence: ApiUsageConfidence::Low,
     }
 }
 
-#[tauri::command]
-async fn get_api_usage(
-    provider: String,
-    state: State<'_, AppState>,
-) -> Result<Option<ApiUsage>, String> {
+/// Estimate provider activity, preferring the structured `activity.ndjson`
+/// log and falling back to the legacy keyword parser when it's absent.
+fn estimate_provider_activity(
+    state: &AppState,
+    provider: &str,
+    range_hours: u64,
+) -> ProviderActivity {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis() as i64;
+    let cutoff = now - (range_hours as i64 * 3600 * 1000);
 
-    let profiles = read_auth_profiles()?;
+    read_structured_activity(state, provider, cutoff)
+        .unwrap_or_else(|| estimate_provider_activity_from_keywords(state, provider, cutoff))
+}
 
-    match provider.as_str() {
-        "openrouter" => {
-            let profile_key = "openrouter:default";
-            let api_key = profiles
-                .get("profiles")
-                .and_then(|p| p.get(profile_key))
-                .and_then(|p| p.get("key").and_then(|k| k.as_str()));
-
-            if let Some(key) = api_key {
-                let client = reqwest::Client::new();
-                let response = client
-                    .get("https://openrouter.ai/api/v1/auth/key")
-                    .header("Authorization", format!("Bearer {}", key))
-                    .send()
-                    .await
-                    .map_err(|e| format!("Failed to fetch usage: {}", e))?;
-
-                if response.status().is_success() {
-                    let json: serde_json::Value = response
-                        .json()
-                        .await
-                        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-                    let data = json.get("data").unwrap_or(&serde_json::Value::Null);
-                    let limit_remaining = data
-                        .get("limit_remaining")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(0);
-                    let usage = data.get("usage").and_then(|v| v.as_u64()).unwrap_or(0);
-                    let limit = limit_remaining + usage;
-
-                    return Ok(Some(ApiUsage {
-                        provider: provider.clone(),
-                        used: usage,
-                        limit,
-                        remaining: limit_remaining,
-                        unit: "credits".to_string(),
-                        reset_at: None,
-                        fetched_at: now,
-                        source: "real_api".to_string(),
-                        confidence: "high".to_string(),
-                        reason: None,
-                        activity_calls_24h: None,
-                        activity_errors_24h: None,
-                        activity_success_rate: None,
-                        activity_last_seen_at: None,
-                    }));
-                }
-            }
-            // Fallback to estimated activity if API call failed
-            let activity = estimate_provider_activity(&state, &provider, 24);
-            if activity.calls > 0 {
-                let success_rate = if activity.calls > 0 {
-                    Some(((activity.calls - activity.errors) as f64) / (activity.calls as f64))
-                } else {
-                    None
-                };
-                Ok(Some(ApiUsage {
-                    provider: provider.clone(),
-                    used: 0,
-                    limit: 0,
-                    remaining: 0,
-                    unit: "credits".to_string(),
-                    reset_at: None,
-                    fetched_at: now,
-                    source: "estimated_activity".to_string(),
-                    confidence: "low".to_string(),
-                    reason: Some("API query failed, using log-based estimation".to_string()),
-                    activity_calls_24h: Some(activity.calls),
-                    activity_errors_24h: Some(activity.errors),
-                    activity_success_rate: success_rate,
-                    activity_last_seen_at: activity.last_seen_at,
-                }))
-            } else {
-                Ok(None)
+// ============================================================================
+// Provider Usage Sources
+// ============================================================================
+
+/// A pluggable source of *real* usage/quota data for one provider, queried
+/// against that provider's own billing/usage API. `get_api_usage_inner`
+/// tries the registered source first and falls back to
+/// `estimate_provider_activity`'s log-based heuristics when a provider has
+/// no source, the source has no credential configured, or the call fails.
+#[async_trait::async_trait]
+trait ProviderUsageSource: Send + Sync {
+    /// Provider id this source answers for, e.g. `"openrouter"`.
+    fn provider_id(&self) -> &'static str;
+
+    /// Unit label used for the log-estimation fallback when this source
+    /// comes up empty.
+    fn fallback_unit(&self) -> &'static str {
+        "requests"
+    }
+
+    /// Fetch current usage from the provider's live API. `Ok(None)` means
+    /// no usable credential is configured; transport/parse failures are an
+    /// `Err` so the caller knows to fall back rather than report zero usage.
+    async fn fetch(
+        &self,
+        profiles: &serde_json::Value,
+        now: i64,
+    ) -> Result<Option<ApiUsage>, String>;
+}
+
+struct OpenRouterUsage;
+
+#[async_trait::async_trait]
+impl ProviderUsageSource for OpenRouterUsage {
+    fn provider_id(&self) -> &'static str {
+        "openrouter"
+    }
+
+    fn fallback_unit(&self) -> &'static str {
+        "credits"
+    }
+
+    async fn fetch(
+        &self,
+        profiles: &serde_json::Value,
+        now: i64,
+    ) -> Result<Option<ApiUsage>, String> {
+        let api_key = profiles
+            .get("profiles")
+            .and_then(|p| p.get("openrouter:default"))
+            .and_then(|p| p.get("key").and_then(|k| k.as_str()))
+            .map(decrypt_secret)
+            .transpose()
+            .map_err(|e| format!("Stored API key is corrupt: {}", e))?;
+
+        let Some(key) = api_key else {
+            return Ok(None);
+        };
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get("https://openrouter.ai/api/v1/auth/key")
+            .header("Authorization", format!("Bearer {}", key))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch usage: {}", e))?;
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        let data = json.get("data").unwrap_or(&serde_json::Value::Null);
+        let limit_remaining = data
+            .get("limit_remaining")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let usage = data.get("usage").and_then(|v| v.as_u64()).unwrap_or(0);
+
+        Ok(Some(ApiUsage {
+            provider: self.provider_id().to_string(),
+            used: usage,
+            limit: limit_remaining + usage,
+            remaining: limit_remaining,
+            unit: "credits".to_string(),
+            reset_at: None,
+            fetched_at: now,
+            source: ApiUsageSource::RealApi,
+            confidence: ApiUsageConfidence::High,
+            reason: None,
+            activity_calls_24h: None,
+            activity_errors_24h: None,
+            activity_success_rate: None,
+            activity_last_seen_at: None,
+            activity_rate_limited_24h: None,
+            activity_p50_latency_ms: None,
+            activity_p95_latency_ms: None,
+        }))
+    }
+}
+
+/// Queries Google Cloud Billing's budgets API for the Antigravity proxy's
+/// linked billing account using the stored OAuth access token. Requires a
+/// `billing_account_id` field on the auth profile (not currently written by
+/// anything in this repo), so this falls back to log estimation until that
+/// field is populated some other way.
+struct GoogleBillingUsage;
+
+#[async_trait::async_trait]
+impl ProviderUsageSource for GoogleBillingUsage {
+    fn provider_id(&self) -> &'static str {
+        "google-antigravity"
+    }
+
+    async fn fetch(
+        &self,
+        profiles: &serde_json::Value,
+        now: i64,
+    ) -> Result<Option<ApiUsage>, String> {
+        let Some((_key, profile)) = find_active_profile(profiles, self.provider_id(), now) else {
+            return Ok(None);
+        };
+
+        let Some(billing_account) = profile.get("billing_account_id").and_then(|b| b.as_str())
+        else {
+            return Ok(None);
+        };
+
+        let access_token = match profile.get("access").and_then(|a| a.as_str()) {
+            Some(token) => {
+                decrypt_secret(token).map_err(|e| format!("Stored access token is corrupt: {}", e))?
             }
+            None => return Ok(None),
+        };
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(format!(
+                "https://cloudbilling.googleapis.com/v1/billingAccounts/{}/budgets",
+                billing_account
+            ))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch usage: {}", e))?;
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        let spend = json
+            .get("budgets")
+            .and_then(|b| b.as_array())
+            .and_then(|arr| arr.first())
+            .and_then(|b| b.get("amount"))
+            .and_then(|a| a.get("specifiedAmount"))
+            .and_then(|a| a.get("units"))
+            .and_then(|u| u.as_str())
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        Ok(Some(ApiUsage {
+            provider: self.provider_id().to_string(),
+            used: spend,
+            limit: spend,
+            remaining: 0,
+            unit: "usd".to_string(),
+            reset_at: None,
+            fetched_at: now,
+            source: ApiUsageSource::RealApi,
+            confidence: ApiUsageConfidence::High,
+            reason: None,
+            activity_calls_24h: None,
+            activity_errors_24h: None,
+            activity_success_rate: None,
+            activity_last_seen_at: None,
+            activity_rate_limited_24h: None,
+            activity_p50_latency_ms: None,
+            activity_p95_latency_ms: None,
+        }))
+    }
+}
+
+/// Generic adapter for providers that expose an OpenAI-style usage/billing
+/// endpoint behind the same API key used for chat completions.
+struct OpenAiCompatibleUsage {
+    provider: &'static str,
+    usage_endpoint: &'static str,
+}
+
+#[async_trait::async_trait]
+impl ProviderUsageSource for OpenAiCompatibleUsage {
+    fn provider_id(&self) -> &'static str {
+        self.provider
+    }
+
+    async fn fetch(
+        &self,
+        profiles: &serde_json::Value,
+        now: i64,
+    ) -> Result<Option<ApiUsage>, String> {
+        let profile_key = format!("{}:default", self.provider);
+        let api_key = profiles
+            .get("profiles")
+            .and_then(|p| p.get(&profile_key))
+            .and_then(|p| p.get("key").and_then(|k| k.as_str()))
+            .map(decrypt_secret)
+            .transpose()
+            .map_err(|e| format!("Stored API key is corrupt: {}", e))?;
+
+        let Some(key) = api_key else {
+            return Ok(None);
+        };
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(self.usage_endpoint)
+            .header("Authorization", format!("Bearer {}", key))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch usage: {}", e))?;
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        let used = json.get("total_usage").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let limit = json.get("hard_limit_usd").and_then(|v| v.as_f64());
+
+        Ok(Some(ApiUsage {
+            provider: self.provider.to_string(),
+            used: used as u64,
+            limit: limit.unwrap_or(used) as u64,
+            remaining: limit.map(|l| (l - used).max(0.0) as u64).unwrap_or(0),
+            unit: "usd".to_string(),
+            reset_at: None,
+            fetched_at: now,
+            source: ApiUsageSource::RealApi,
+            confidence: ApiUsageConfidence::High,
+            reason: None,
+            activity_calls_24h: None,
+            activity_errors_24h: None,
+            activity_success_rate: None,
+            activity_last_seen_at: None,
+            activity_rate_limited_24h: None,
+            activity_p50_latency_ms: None,
+            activity_p95_latency_ms: None,
+        }))
+    }
+}
+
+struct UsageSourceRegistry {
+    sources: Vec<Box<dyn ProviderUsageSource>>,
+}
+
+impl UsageSourceRegistry {
+    fn new() -> Self {
+        UsageSourceRegistry {
+            sources: vec![
+                Box::new(OpenRouterUsage),
+                Box::new(GoogleBillingUsage),
+                Box::new(OpenAiCompatibleUsage {
+                    provider: "moonshot",
+                    usage_endpoint: "https://api.moonshot.ai/v1/users/me/balance",
+                }),
+                Box::new(OpenAiCompatibleUsage {
+                    provider: "zai",
+                    usage_endpoint: "https://open.bigmodel.cn/api/paas/v4/usage",
+                }),
+            ],
+        }
+    }
+
+    fn get(&self, provider: &str) -> Option<&dyn ProviderUsageSource> {
+        self.sources
+            .iter()
+            .map(|s| s.as_ref())
+            .find(|s| s.provider_id() == provider)
+    }
+}
+
+fn usage_source_registry() -> UsageSourceRegistry {
+    UsageSourceRegistry::new()
+}
+
+/// Builds a dual-track `ApiUsage` from local call-log estimation, used
+/// whenever live usage data isn't available for `provider`.
+fn estimated_usage_from_activity(
+    provider: &str,
+    unit: &str,
+    confidence: ApiUsageConfidence,
+    reason: String,
+    activity: &ProviderActivity,
+    now: i64,
+) -> ApiUsage {
+    let success_rate = if activity.calls > 0 {
+        Some(((activity.calls - activity.errors) as f64) / (activity.calls as f64))
+    } else {
+        None
+    };
+    ApiUsage {
+        provider: provider.to_string(),
+        used: 0,
+        limit: 0,
+        remaining: 0,
+        unit: unit.to_string(),
+        reset_at: None,
+        fetched_at: now,
+        source: ApiUsageSource::EstimatedActivity,
+        confidence,
+        reason: Some(reason),
+        activity_calls_24h: Some(activity.calls),
+        activity_errors_24h: Some(activity.errors),
+        activity_success_rate: success_rate,
+        activity_last_seen_at: activity.last_seen_at,
+        activity_rate_limited_24h: Some(activity.rate_limited),
+        activity_p50_latency_ms: activity.p50_latency_ms,
+        activity_p95_latency_ms: activity.p95_latency_ms,
+    }
+}
+
+fn unsupported_usage(provider: &str, unit: &str, reason: String, now: i64) -> ApiUsage {
+    ApiUsage {
+        provider: provider.to_string(),
+        used: 0,
+        limit: 0,
+        remaining: 0,
+        unit: unit.to_string(),
+        reset_at: None,
+        fetched_at: now,
+        source: ApiUsageSource::Unsupported,
+        confidence: ApiUsageConfidence::Low,
+        reason: Some(reason),
+        activity_calls_24h: Some(0),
+        activity_errors_24h: Some(0),
+        activity_success_rate: None,
+        activity_last_seen_at: None,
+        activity_rate_limited_24h: None,
+        activity_p50_latency_ms: None,
+        activity_p95_latency_ms: None,
+    }
+}
+
+async fn get_api_usage_inner(
+    state: &AppState,
+    provider: &str,
+) -> Result<Option<ApiUsage>, String> {
+    let provider = provider.to_string();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+
+    // Opportunistically refresh this provider's OAuth token if it's expired
+    // or about to be; a refresh failure here just leaves it expired.
+    if oauth_provider_config(&provider).is_some() {
+        let needs_refresh = find_active_profile(&read_auth_profiles()?, &provider, now)
+            .map(|(_key, p)| oauth_needs_refresh(p, now))
+            .unwrap_or(false);
+        if needs_refresh {
+            let _ = refresh_oauth_token_inner(state, &provider).await;
         }
-        // All estimatable providers (moonshot, zai, openai-codex, google-antigravity, etc.)
-        pid
-        @ ("moonshot" | "zai" | "openai-codex" | "google-antigravity" | "google" | "gemini") => {
-            let profile_key = format!("{}:default", pid);
-            let has_key = profiles
-                .get("profiles")
-                .and_then(|p| p.get(&profile_key))
-                .is_some();
+    }
+
+    let profiles = load_auth_profiles_migrated()?;
+
+    // Try the provider's registered real-usage source first; fall back to
+    // log-based estimation if there isn't one, it has no credential, or it
+    // failed.
+    if let Some(source) = usage_source_registry().get(&provider) {
+        if let Ok(Some(usage)) = source.fetch(&profiles, now).await {
+            return Ok(Some(usage));
+        }
+
+        let activity = estimate_provider_activity(state, &provider, 24);
+        let has_key = find_active_profile(&profiles, &provider, now).is_some();
+        return Ok(if activity.calls > 0 {
+            Some(estimated_usage_from_activity(
+                &provider,
+                source.fallback_unit(),
+                activity.confidence,
+                "Real usage API unavailable; using log-based estimation".to_string(),
+                &activity,
+                now,
+            ))
+        } else if has_key {
+            Some(unsupported_usage(
+                &provider,
+                source.fallback_unit(),
+                "Real usage API unavailable and no recent local activity found".to_string(),
+                now,
+            ))
+        } else {
+            None
+        });
+    }
 
-            // Try to estimate from logs
-            let activity = estimate_provider_activity(&state, pid, 24);
+    match provider.as_str() {
+        // Providers with no registered usage source (no public usage API).
+        pid @ ("openai-codex" | "google" | "gemini") => {
+            let has_key = find_active_profile(&profiles, pid, now).is_some();
+            let activity = estimate_provider_activity(state, pid, 24);
 
             if activity.calls > 0 {
-                // We have activity data from logs
-                let success_rate = if activity.calls > 0 {
-                    Some(((activity.calls - activity.errors) as f64) / (activity.calls as f64))
-                } else {
-                    None
-                };
-                let confidence = if activity.calls >= 10 {
-                    "medium"
+                let confidence = if activity.confidence == ApiUsageConfidence::High {
+                    ApiUsageConfidence::High
+                } else if activity.calls >= 10 {
+                    ApiUsageConfidence::Medium
                 } else {
-                    "low"
+                    ApiUsageConfidence::Low
                 };
-
-                Ok(Some(ApiUsage {
-                    provider: provider.clone(),
-                    used: 0,
-                    limit: 0,
-                    remaining: 0,
-                    unit: "requests".to_string(),
-                    reset_at: None,
-                    fetched_at: now,
-                    source: "estimated_activity".to_string(),
-                    confidence: confidence.to_string(),
-                    reason: Some(format!(
+                Ok(Some(estimated_usage_from_activity(
+                    pid,
+                    "requests",
+                    confidence,
+                    format!(
                         "Provider has no public usage API; estimated from {} log entries in 24h",
                         activity.calls
-                    )),
-                    activity_calls_24h: Some(activity.calls),
-                    activity_errors_24h: Some(activity.errors),
-                    activity_success_rate: success_rate,
-                    activity_last_seen_at: activity.last_seen_at,
-                }))
-            } else if has_key {
-                // Has key but no recent activity
-                Ok(Some(ApiUsage {
-                    provider: provider.clone(),
-                    used: 0,
-                    limit: 0,
-                    remaining: 0,
-                    unit: "requests".to_string(),
-                    reset_at: None,
-                    fetched_at: now,
-                    source: "unsupported".to_string(),
-                    confidence: "low".to_string(),
-                    reason: Some(
-                        "Provider has no public usage API and no recent local activity found"
-                            .to_string(),
                     ),
-                    activity_calls_24h: Some(0),
-                    activity_errors_24h: Some(0),
-                    activity_success_rate: None,
-                    activity_last_seen_at: None,
-                }))
+                    &activity,
+                    now,
+                )))
+            } else if has_key {
+                Ok(Some(unsupported_usage(
+                    pid,
+                    "requests",
+                    "Provider has no public usage API and no recent local activity found"
+                        .to_string(),
+                    now,
+                )))
             } else {
-                // No key configured
                 Ok(None)
             }
         }
@@ -5483,49 +10923,1494 @@ async fn get_api_usage(
 }
 
 #[tauri::command]
-fn set_api_key(provider: String, key: String) -> Result<(), String> {
-    let mut profiles = read_auth_profiles()?;
+async fn get_api_usage(
+    provider: String,
+    state: State<'_, AppState>,
+) -> Result<Option<ApiUsage>, String> {
+    get_api_usage_inner(&state, &provider).await
+}
 
-    let profile_key = format!("{}:default", provider);
-    let profiles_obj = profiles
-        .get_mut("profiles")
+/// Create or overwrite a named API-key profile, preserving its
+/// `created_at`/`description`/`expires_at` across a key rotation unless new
+/// values are supplied.
+fn create_api_key_profile_inner(
+    provider: &str,
+    name: &str,
+    key: &str,
+    description: Option<String>,
+    expires_at: Option<i64>,
+) -> Result<(), String> {
+    let mut profiles = read_auth_profiles()?;
+    let encrypted_key = encrypt_secret(key)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+
+    let profile_key = format!("{}:{}", provider, name);
+    let profiles_obj = profiles
+        .get_mut("profiles")
         .ok_or("Invalid profiles structure")?
         .as_object_mut()
         .ok_or("Profiles is not an object")?;
 
+    let existing = profiles_obj.get(&profile_key);
+    let created_at = existing
+        .and_then(|p| p.get("created_at"))
+        .and_then(|c| c.as_i64())
+        .unwrap_or(now);
+
     profiles_obj.insert(
         profile_key,
         serde_json::json!({
             "type": "api_key",
             "provider": provider,
-            "key": key
+            "key": encrypted_key,
+            "name": name,
+            "description": description,
+            "created_at": created_at,
+            "updated_at": now,
+            "expires_at": expires_at,
         }),
     );
 
-    write_auth_profiles(&profiles)
+    write_auth_profiles(&profiles)
+}
+
+#[tauri::command]
+fn set_api_key(provider: String, key: String, name: Option<String>) -> Result<(), String> {
+    let name = name.unwrap_or_else(|| "default".to_string());
+    let profile_key = format!("{}:{}", provider, name);
+    let profiles = read_auth_profiles()?;
+    let existing = profiles
+        .get("profiles")
+        .and_then(|p| p.get(&profile_key));
+    let description = existing
+        .and_then(|p| p.get("description"))
+        .and_then(|d| d.as_str().map(|s| s.to_string()));
+    let expires_at = existing
+        .and_then(|p| p.get("expires_at"))
+        .and_then(|e| e.as_i64());
+
+    create_api_key_profile_inner(&provider, &name, &key, description, expires_at)
+}
+
+#[tauri::command]
+fn create_api_key_profile(
+    provider: String,
+    name: String,
+    key: String,
+    description: Option<String>,
+    expires_at: Option<i64>,
+) -> Result<(), String> {
+    create_api_key_profile_inner(&provider, &name, &key, description, expires_at)
+}
+
+#[tauri::command]
+fn list_api_key_profiles(provider: String) -> Result<Vec<ApiKeyProfile>, String> {
+    let profiles = read_auth_profiles()?;
+    let prefix = format!("{}:", provider);
+    let mut result = Vec::new();
+
+    if let Some(profiles_obj) = profiles.get("profiles").and_then(|p| p.as_object()) {
+        for (key, p) in profiles_obj {
+            if !key.starts_with(&prefix) || p.get("type").and_then(|t| t.as_str()) != Some("api_key")
+            {
+                continue;
+            }
+            let name = p
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| key[prefix.len()..].to_string());
+            result.push(ApiKeyProfile {
+                provider: provider.clone(),
+                name,
+                description: p
+                    .get("description")
+                    .and_then(|d| d.as_str().map(|s| s.to_string())),
+                created_at: p.get("created_at").and_then(|c| c.as_i64()).unwrap_or(0),
+                updated_at: p.get("updated_at").and_then(|u| u.as_i64()).unwrap_or(0),
+                expires_at: p.get("expires_at").and_then(|e| e.as_i64()),
+                has_key: p.get("key").and_then(|k| k.as_str()).is_some(),
+            });
+        }
+    }
+
+    result.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(result)
+}
+
+#[tauri::command]
+fn delete_api_key_profile(provider: String, name: String) -> Result<(), String> {
+    let mut profiles = read_auth_profiles()?;
+
+    let profile_key = format!("{}:{}", provider, name);
+    if let Some(profiles_obj) = profiles.get_mut("profiles").and_then(|p| p.as_object_mut()) {
+        profiles_obj.remove(&profile_key);
+    }
+
+    write_auth_profiles(&profiles)
+}
+
+#[tauri::command]
+fn delete_api_key(provider: String) -> Result<(), String> {
+    let mut profiles = read_auth_profiles()?;
+
+    let profile_key = format!("{}:default", provider);
+    if let Some(profiles_obj) = profiles.get_mut("profiles").and_then(|p| p.as_object_mut()) {
+        profiles_obj.remove(&profile_key);
+    }
+
+    write_auth_profiles(&profiles)
+}
+
+// ============================================================================
+// Entry Point
+// ============================================================================
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+// ============================================================================
+// Crash Reporting
+// ============================================================================
+
+const CRASH_STDERR_TAIL_LINES: usize = 40;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub kind: String, // "panic" | "dispatcher_exit"
+    pub timestamp: String,
+    pub app_version: String,
+    pub os: String,
+    pub thread: String,
+    pub message: String,
+    pub frames: Vec<String>,
+    pub argv: Option<Vec<String>>,
+    pub exit_code: Option<i32>,
+    pub stderr_tail: Option<Vec<String>>,
+    pub uploaded: bool,
+}
+
+fn crash_reports_dir(state: &AppState) -> PathBuf {
+    PathBuf::from(&state.logs_dir).join("crash_reports")
+}
+
+fn demangled_backtrace_frames() -> Vec<String> {
+    let bt = backtrace::Backtrace::new();
+    let mut frames = Vec::new();
+    for frame in bt.frames() {
+        for symbol in frame.symbols() {
+            if let Some(name) = symbol.name() {
+                frames.push(rustc_demangle::demangle(&name.to_string()).to_string());
+            }
+        }
+    }
+    frames
+}
+
+fn persist_crash_report(state: &AppState, report: &CrashReport) -> Result<PathBuf, String> {
+    let dir = crash_reports_dir(state);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create crash_reports dir: {}", e))?;
+    let path = dir.join(format!("{}.json", report.id));
+    let content = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write crash report: {}", e))?;
+    Ok(path)
+}
+
+/// Installs a panic hook that captures a demangled backtrace and persists it
+/// under `logs_dir/crash_reports` before the default hook prints to stderr,
+/// then fires off a best-effort S3 upload if a crash sink is configured.
+fn install_panic_hook(logs_dir: String, config_path: String) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        let thread = std::thread::current()
+            .name()
+            .unwrap_or("unnamed")
+            .to_string();
+        let report = CrashReport {
+            id: format!("panic-{}", now_epoch_ms()),
+            kind: "panic".to_string(),
+            timestamp: now_iso(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            thread,
+            message: message.clone(),
+            frames: demangled_backtrace_frames(),
+            argv: None,
+            exit_code: None,
+            stderr_tail: None,
+            uploaded: false,
+        };
+
+        let dir = PathBuf::from(&logs_dir).join("crash_reports");
+        if fs::create_dir_all(&dir).is_ok() {
+            if let Ok(content) = serde_json::to_string_pretty(&report) {
+                let _ = fs::write(dir.join(format!("{}.json", report.id)), content);
+            }
+        }
+
+        let env_map = read_env_map(&PathBuf::from(&config_path).join(".env.v4.local"));
+        spawn_best_effort_crash_upload(env_map, report);
+
+        default_hook(info);
+    }));
+}
+
+/// Records a crash report for a non-zero dispatcher exit so failures show up
+/// alongside panics instead of only as swallowed subprocess stderr, then
+/// fires off a best-effort S3 upload if a crash sink is configured.
+fn capture_dispatcher_crash(state: &AppState, argv: &[String], exit_code: i32, stderr: &str) {
+    let mut stderr_tail: Vec<String> = stderr.lines().map(|s| s.to_string()).collect();
+    if stderr_tail.len() > CRASH_STDERR_TAIL_LINES {
+        let skip = stderr_tail.len() - CRASH_STDERR_TAIL_LINES;
+        stderr_tail.drain(0..skip);
+    }
+
+    let report = CrashReport {
+        id: format!("dispatcher-{}", now_epoch_ms()),
+        kind: "dispatcher_exit".to_string(),
+        timestamp: now_iso(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        thread: "main".to_string(),
+        message: format!("dispatcher exited with code {}", exit_code),
+        frames: Vec::new(),
+        argv: Some(argv.to_vec()),
+        exit_code: Some(exit_code),
+        stderr_tail: Some(stderr_tail),
+        uploaded: false,
+    };
+
+    if let Err(e) = persist_crash_report(state, &report) {
+        eprintln!("[crash-report] failed to persist dispatcher crash: {}", e);
+    }
+
+    spawn_best_effort_crash_upload(crash_sink_env(state), report);
+}
+
+fn crash_sink_env(state: &AppState) -> HashMap<String, String> {
+    let env_path = PathBuf::from(&state.config_path).join(".env.v4.local");
+    read_env_map(&env_path)
+}
+
+fn s3_presign_put_url(
+    endpoint: &str,
+    bucket: &str,
+    region: &str,
+    access_key: &str,
+    secret_key: &str,
+    object_key: &str,
+) -> String {
+    use hmac::{Hmac, Mac};
+    use sha2::{Digest, Sha256};
+    type HmacSha256 = Hmac<Sha256>;
+
+    fn sign(key: &[u8], msg: &str) -> Vec<u8> {
+        let mut mac = HmacSha256::new_from_slice(key).expect("hmac accepts any key length");
+        mac.update(msg.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+    fn url_encode(s: &str) -> String {
+        s.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~') {
+                    c.to_string()
+                } else {
+                    format!("%{:02X}", c as u32)
+                }
+            })
+            .collect()
+    }
+
+    let now = Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date_stamp = now.format("%Y%m%d").to_string();
+    let host = endpoint
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, region);
+    let credential = url_encode(&format!("{}/{}", access_key, credential_scope));
+    // One-hour lifetime; the bucket's lifecycle rule expires the object itself.
+    let canonical_query = format!(
+        "X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential={}&X-Amz-Date={}&X-Amz-Expires=3600&X-Amz-SignedHeaders=host",
+        credential, amz_date
+    );
+    let canonical_request = format!(
+        "PUT\n/{}/{}\n{}\nhost:{}\n\nhost\nUNSIGNED-PAYLOAD",
+        bucket, object_key, canonical_query, host
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_request.as_bytes());
+    let hashed_request = hex::encode(hasher.finalize());
+
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date, credential_scope, hashed_request
+    );
+
+    let k_date = sign(format!("AWS4{}", secret_key).as_bytes(), &date_stamp);
+    let k_region = sign(&k_date, region);
+    let k_service = sign(&k_region, "s3");
+    let k_signing = sign(&k_service, "aws4_request");
+    let signature = hex::encode(sign(&k_signing, &string_to_sign));
+
+    format!(
+        "{}/{}/{}?{}&X-Amz-Signature={}",
+        endpoint, bucket, object_key, canonical_query, signature
+    )
+}
+
+async fn upload_crash_report_s3(state: &AppState, report: &CrashReport) -> Result<(), String> {
+    let env_map = crash_sink_env(state);
+    upload_crash_report_s3_with_env(&env_map, report).await
+}
+
+/// Does the actual S3 PUT; split out from `upload_crash_report_s3` so
+/// best-effort upload attempts from sync contexts (the panic hook,
+/// `capture_dispatcher_crash`) that only have a `crash_sink_env` map handy
+/// -- not a live `&AppState` they can carry across a spawned task -- can
+/// call it without needing an `AppHandle`.
+async fn upload_crash_report_s3_with_env(
+    env_map: &HashMap<String, String>,
+    report: &CrashReport,
+) -> Result<(), String> {
+    let endpoint = env_map
+        .get("CRASH_S3_ENDPOINT")
+        .cloned()
+        .ok_or("S3 crash sink not configured (CRASH_S3_ENDPOINT unset)")?;
+    let bucket = env_map
+        .get("CRASH_S3_BUCKET")
+        .cloned()
+        .ok_or("S3 crash sink not configured (CRASH_S3_BUCKET unset)")?;
+    let region = env_map
+        .get("CRASH_S3_REGION")
+        .cloned()
+        .unwrap_or_else(|| "us-east-1".to_string());
+    let access_key = env_map
+        .get("CRASH_S3_ACCESS_KEY")
+        .cloned()
+        .ok_or("S3 crash sink not configured (CRASH_S3_ACCESS_KEY unset)")?;
+    let secret_key = env_map
+        .get("CRASH_S3_SECRET_KEY")
+        .cloned()
+        .ok_or("S3 crash sink not configured (CRASH_S3_SECRET_KEY unset)")?;
+
+    let object_key = format!("crash-reports/{}.json", report.id);
+    let url = s3_presign_put_url(
+        &endpoint,
+        &bucket,
+        &region,
+        &access_key,
+        &secret_key,
+        &object_key,
+    );
+    let body = serde_json::to_vec(report)
+        .map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .put(&url)
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload crash report to S3: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "S3 crash report upload failed with status {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+/// Fires off `upload_crash_report_s3_with_env` without waiting on it, from
+/// sync call sites (the panic hook, `capture_dispatcher_crash`) that want
+/// upload-on-capture rather than relying on a human opening the UI and
+/// clicking retry. Guarded with `try_current` rather than a bare
+/// `tokio::spawn` because the panic hook can fire before the Tauri runtime
+/// exists (it's installed ahead of `tauri::Builder`) or on a thread that
+/// never entered it; in that case the report is still on disk (both call
+/// sites write it before calling this) and `retry_crash_upload` can send it
+/// later.
+fn spawn_best_effort_crash_upload(env_map: HashMap<String, String>, report: CrashReport) {
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        handle.spawn(async move {
+            let _ = upload_crash_report_s3_with_env(&env_map, &report).await;
+        });
+    }
+}
+
+async fn forward_crash_report_clickhouse(
+    state: &AppState,
+    report: &CrashReport,
+) -> Result<(), String> {
+    let env_map = crash_sink_env(state);
+    let base_url = env_map
+        .get("CRASH_CLICKHOUSE_URL")
+        .cloned()
+        .ok_or("ClickHouse crash sink not configured (CRASH_CLICKHOUSE_URL unset)")?;
+    let table = env_map
+        .get("CRASH_CLICKHOUSE_TABLE")
+        .cloned()
+        .unwrap_or_else(|| "crash_reports".to_string());
+
+    let row = serde_json::to_string(report)
+        .map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!(
+            "{}/?query=INSERT INTO {} FORMAT JSONEachRow",
+            base_url.trim_end_matches('/'),
+            table
+        ))
+        .body(row)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to forward crash report to ClickHouse: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "ClickHouse crash report insert failed with status {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn list_crash_reports(state: State<'_, AppState>) -> Result<Vec<CrashReport>, String> {
+    let dir = crash_reports_dir(&state);
+    let mut reports = Vec::new();
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(reports);
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(report) = serde_json::from_str::<CrashReport>(&content) {
+                reports.push(report);
+            }
+        }
+    }
+    reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(reports)
+}
+
+#[tauri::command]
+async fn retry_crash_upload(report_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let path = crash_reports_dir(&state).join(format!("{}.json", report_id));
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read crash report: {}", e))?;
+    let mut report: CrashReport = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse crash report: {}", e))?;
+
+    upload_crash_report_s3(&state, &report).await?;
+    // ClickHouse aggregation is best-effort; don't fail the retry if it's unreachable.
+    let _ = forward_crash_report_clickhouse(&state, &report).await;
+
+    report.uploaded = true;
+    persist_crash_report(&state, &report)?;
+    Ok(())
+}
+
+// ============================================================================
+// Status/Availability Watcher (push updates instead of UI polling)
+// ============================================================================
+
+const STATUS_WATCHER_TICK_MS: u64 = 2_000;
+const STATUS_WATCHER_HEARTBEAT_MS: i64 = 15_000;
+/// A change must hold for this many consecutive ticks before it's emitted,
+/// so a service flapping during a restart window doesn't spam subscribers.
+const STATUS_WATCHER_STABLE_TICKS: u32 = 2;
+
+#[derive(Debug, Clone)]
+struct WatcherSnapshot {
+    services: Vec<ServiceStatus>,
+    availability: Option<ModelAvailabilityReport>,
+}
+
+impl WatcherSnapshot {
+    /// Fingerprint used to decide whether two ticks represent the same
+    /// state. Deliberately drops fields that change every tick regardless
+    /// of anything meaningful happening (`uptime` strings, `fetched_at`),
+    /// so those don't defeat the diffing.
+    fn fingerprint(&self) -> String {
+        let services_fp: Vec<_> = self
+            .services
+            .iter()
+            .map(|s| (s.name.clone(), s.status.clone(), s.pid, s.restarts))
+            .collect();
+        let availability_fp = self.availability.as_ref().map(|a| {
+            let mut value = serde_json::to_value(a).unwrap_or(serde_json::Value::Null);
+            if let Some(obj) = value.as_object_mut() {
+                obj.remove("fetched_at");
+            }
+            value
+        });
+        format!("{:?}|{:?}", services_fp, availability_fp)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StatusStreamEvent {
+    seq: u64,
+    emitted_at: i64,
+    services: Vec<ServiceStatus>,
+    availability: Option<ModelAvailabilityReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StatusStreamHeartbeat {
+    seq: u64,
+    emitted_at: i64,
+}
+
+fn next_cooldown_deadline_ms(availability: &Option<ModelAvailabilityReport>) -> Option<i64> {
+    availability.as_ref().and_then(|report| {
+        report
+            .agents
+            .values()
+            .flat_map(|agent| agent.route.iter())
+            .filter_map(|route| route.cooldown_until_ms)
+            .min()
+    })
+}
+
+/// Background task spawned from `setup()`: recomputes service status and
+/// model availability on a tick, diffs against the last *emitted* snapshot,
+/// and pushes `status-stream` events to the desktop UI instead of making it
+/// poll. Emits a lightweight heartbeat when nothing changed so a subscriber
+/// can tell the stream is still alive, and wakes up early when a provider's
+/// `cooldown_until_ms` elapses so recovery shows up without waiting for the
+/// next tick.
+async fn run_status_watcher(app: tauri::AppHandle) {
+    let mut seq: u64 = 0;
+    let mut last_emitted_fp: Option<String> = None;
+    let mut pending_fp: Option<(String, u32)> = None;
+    let mut last_heartbeat_ms = now_epoch_ms();
+
+    loop {
+        let state = app.state::<AppState>();
+        let services = get_service_status_inner(&state).unwrap_or_default();
+        let availability = get_cached_availability_report(&state, false).ok();
+        let snapshot = WatcherSnapshot {
+            services,
+            availability,
+        };
+        let fingerprint = snapshot.fingerprint();
+
+        pending_fp = Some(match pending_fp {
+            Some((fp, ticks)) if fp == fingerprint => (fp, ticks + 1),
+            _ => (fingerprint.clone(), 1),
+        });
+
+        let now_ms = now_epoch_ms();
+        if let Some((fp, ticks)) = &pending_fp {
+            if *ticks >= STATUS_WATCHER_STABLE_TICKS && last_emitted_fp.as_ref() != Some(fp) {
+                seq += 1;
+                let _ = app.emit(
+                    "status-stream",
+                    StatusStreamEvent {
+                        seq,
+                        emitted_at: now_ms,
+                        services: snapshot.services.clone(),
+                        availability: snapshot.availability.clone(),
+                    },
+                );
+                last_emitted_fp = Some(fp.clone());
+                last_heartbeat_ms = now_ms;
+            }
+        }
+
+        if now_ms.saturating_sub(last_heartbeat_ms) >= STATUS_WATCHER_HEARTBEAT_MS {
+            let _ = app.emit(
+                "status-stream-heartbeat",
+                StatusStreamHeartbeat {
+                    seq,
+                    emitted_at: now_ms,
+                },
+            );
+            last_heartbeat_ms = now_ms;
+        }
+
+        let mut sleep_ms = STATUS_WATCHER_TICK_MS;
+        if let Some(deadline_ms) = next_cooldown_deadline_ms(&snapshot.availability) {
+            let until_deadline = deadline_ms.saturating_sub(now_ms).max(0) as u64;
+            sleep_ms = sleep_ms.min(until_deadline.max(200));
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(sleep_ms)).await;
+    }
+}
+
+// ============================================================================
+// Embedded HTTP Admin API
+// ============================================================================
+
+/// Loopback port the admin API listens on; override with
+/// `OPENCLAW_ADMIN_API_PORT` in `.env.v4.local`.
+const ADMIN_API_DEFAULT_PORT: u16 = 4761;
+
+/// `.env.v4.local` key holding the bearer token admin requests must present.
+/// A missing/empty token doesn't stop the listener from starting -- it just
+/// means every request fails authentication, so the desktop app never fails
+/// to launch because this surface isn't configured.
+const ADMIN_API_TOKEN_ENV_KEY: &str = "OPENCLAW_ADMIN_API_TOKEN";
+const ADMIN_API_PORT_ENV_KEY: &str = "OPENCLAW_ADMIN_API_PORT";
+
+struct AdminApiRequest {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    bearer_token: Option<String>,
+    body: Vec<u8>,
+}
+
+fn admin_api_config(state: &AppState) -> (Option<String>, u16) {
+    let env_path = PathBuf::from(&state.config_path).join(".env.v4.local");
+    let env_map = read_env_map(&env_path);
+    let token = env_map
+        .get(ADMIN_API_TOKEN_ENV_KEY)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let port = env_map
+        .get(ADMIN_API_PORT_ENV_KEY)
+        .and_then(|s| s.trim().parse::<u16>().ok())
+        .unwrap_or(ADMIN_API_DEFAULT_PORT);
+    (token, port)
+}
+
+fn admin_api_text_response(status: u16, content_type: &str, payload: &str) -> Vec<u8> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        payload.len(),
+        payload
+    )
+    .into_bytes()
+}
+
+fn admin_api_json_response(status: u16, body: &serde_json::Value) -> Vec<u8> {
+    admin_api_text_response(status, "application/json", &body.to_string())
+}
+
+/// Renders the data `compute_model_availability_report_inner` already
+/// computes, plus the service action counters maintained alongside
+/// `best_effort_audit_operation`, as a Prometheus text exposition so
+/// `runnable_now` going false or a provider's profiles all entering cooldown
+/// can be scraped and alerted on instead of only being visible in the UI.
+async fn render_prometheus_metrics(state: &AppState) -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP agent_runnable Whether the agent has at least one runnable route model (1) or not (0).\n");
+    out.push_str("# TYPE agent_runnable gauge\n");
+    match get_cached_availability_report(state, false) {
+        Ok(report) => {
+            let mut agent_ids: Vec<&String> = report.agents.keys().collect();
+            agent_ids.sort();
+            for agent_id in agent_ids {
+                let availability = &report.agents[agent_id];
+                out.push_str(&format!(
+                    "agent_runnable{{agent=\"{}\"}} {}\n",
+                    agent_id,
+                    if availability.runnable_now { 1 } else { 0 }
+                ));
+            }
+
+            out.push_str("# HELP provider_profiles_total Number of auth profiles configured for the provider.\n");
+            out.push_str("# TYPE provider_profiles_total gauge\n");
+            out.push_str("# HELP provider_profiles_in_cooldown Number of those profiles currently in cooldown.\n");
+            out.push_str("# TYPE provider_profiles_in_cooldown gauge\n");
+            out.push_str("# HELP provider_oauth_valid Whether the provider's OAuth profile is currently valid (1) or not (0).\n");
+            out.push_str("# TYPE provider_oauth_valid gauge\n");
+            out.push_str("# HELP provider_cooldown_until_seconds Unix timestamp (seconds) the provider's cooldown is expected to clear.\n");
+            out.push_str("# TYPE provider_cooldown_until_seconds gauge\n");
+
+            let mut providers: Vec<&String> = report.provider_auth.keys().collect();
+            providers.sort();
+            for provider in providers {
+                let summary = &report.provider_auth[provider];
+                out.push_str(&format!(
+                    "provider_profiles_total{{provider=\"{}\"}} {}\n",
+                    provider, summary.total_profiles
+                ));
+                out.push_str(&format!(
+                    "provider_profiles_in_cooldown{{provider=\"{}\"}} {}\n",
+                    provider, summary.cooldown_profiles
+                ));
+                if summary.oauth_seen {
+                    out.push_str(&format!(
+                        "provider_oauth_valid{{provider=\"{}\"}} {}\n",
+                        provider,
+                        if summary.oauth_has_valid { 1 } else { 0 }
+                    ));
+                }
+                if let Some(until_ms) = summary.cooldown_until_ms {
+                    out.push_str(&format!(
+                        "provider_cooldown_until_seconds{{provider=\"{}\"}} {}\n",
+                        provider,
+                        until_ms / 1000
+                    ));
+                }
+            }
+        }
+        Err(err) => {
+            out.push_str(&format!(
+                "# availability report unavailable: {}\n",
+                err.replace('\n', " ")
+            ));
+        }
+    }
+
+    out.push_str("# HELP service_action_total Count of service start/stop/restart outcomes, by action and status.\n");
+    out.push_str("# TYPE service_action_total counter\n");
+    let counts = state.service_action_counts.lock().unwrap();
+    for action in ["start", "stop", "restart"] {
+        for status in ["success", "failed"] {
+            let count = counts.get(&format!("{}_{}", action, status)).copied().unwrap_or(0);
+            out.push_str(&format!(
+                "service_action_total{{action=\"{}\",status=\"{}\"}} {}\n",
+                action, status, count
+            ));
+        }
+    }
+    drop(counts);
+
+    if let Ok((metrics, queue, alerts, _)) = build_overview_data(state, 24) {
+        if let Ok(services) = get_service_status_inner(state) {
+            out.push_str(&render_overview_prometheus_metrics(&metrics, &queue, &services, &alerts));
+        }
+    }
+
+    out.push_str("# HELP kb_files_total Total files indexed into the knowledge base.\n");
+    out.push_str("# TYPE kb_files_total gauge\n");
+    out.push_str("# HELP kb_chunks_total Total chunks indexed into the knowledge base, by source group.\n");
+    out.push_str("# TYPE kb_chunks_total gauge\n");
+    if let Ok(kb_stats) = get_kb_stats_inner(state) {
+        out.push_str(&format!("kb_files_total {}\n", kb_stats.total_files));
+        for group in &kb_stats.by_source_group {
+            out.push_str(&format!(
+                "kb_chunks_total{{source_group=\"{}\"}} {}\n",
+                prometheus_escape_label(&group.source_group),
+                group.chunk_count
+            ));
+        }
+    }
+
+    out.push_str(
+        "# HELP translation_quality_percent Most recent job's quality metrics, as a percentage.\n",
+    );
+    out.push_str("# TYPE translation_quality_percent gauge\n");
+    if let Some(report) = latest_quality_report_inner(state) {
+        for (metric_label, value) in [
+            ("terminology", report.terminology_hit),
+            ("structure", report.structure_fidelity),
+            ("purity", report.purity_score),
+        ] {
+            out.push_str(&format!(
+                "translation_quality_percent{{metric=\"{}\"}} {}\n",
+                metric_label, value
+            ));
+        }
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    if let Ok(profiles) = load_auth_profiles_migrated() {
+        let providers = compute_api_providers(&profiles, now);
+        let mut activities = HashMap::new();
+        for (id, _name, _auth_type) in get_known_providers() {
+            activities.insert(id.to_string(), estimate_provider_activity(state, id, 24));
+        }
+        let openrouter_usage = get_api_usage_inner(state, "openrouter").await.unwrap_or(None);
+        out.push_str(&render_provider_prometheus_metrics(
+            &providers,
+            &activities,
+            openrouter_usage.as_ref(),
+        ));
+    }
+
+    out
+}
+
+/// Parses just enough of an HTTP/1.1 request off `stream` to route admin
+/// requests: the request line, the `Content-Length`/`Authorization` headers,
+/// and a fixed-length body. Not a general-purpose parser -- chunked request
+/// bodies and keep-alive aren't supported, which is fine for a handful of
+/// short-lived admin clients talking to a loopback port.
+async fn read_admin_api_request(
+    stream: &mut tokio::net::TcpStream,
+) -> Result<AdminApiRequest, String> {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let raw_target = parts.next().unwrap_or("").to_string();
+    if method.is_empty() || raw_target.is_empty() {
+        return Err("malformed request line".to_string());
+    }
+    let (path, query) = match raw_target.split_once('?') {
+        Some((path, query_string)) => (path.to_string(), parse_admin_api_query(query_string)),
+        None => (raw_target, HashMap::new()),
+    };
+
+    let mut content_length: usize = 0;
+    let mut bearer_token = None;
+    loop {
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| e.to_string())?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim().to_ascii_lowercase();
+            let value = value.trim();
+            if name == "content-length" {
+                content_length = value.parse().unwrap_or(0);
+            } else if name == "authorization" {
+                bearer_token = value
+                    .strip_prefix("Bearer ")
+                    .map(|t| t.trim().to_string());
+            }
+        }
+    }
+
+    let mut body = vec![0u8; content_length];
+    if content_length > 0 {
+        reader
+            .read_exact(&mut body)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(AdminApiRequest {
+        method,
+        path,
+        query,
+        bearer_token,
+        body,
+    })
+}
+
+fn parse_admin_api_query(query_string: &str) -> HashMap<String, String> {
+    query_string
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Routes one already-authenticated admin API request to the same `_inner`
+/// helpers the Tauri commands call, so the HTTP surface and the desktop UI
+/// can never drift out of sync.
+async fn route_admin_api_request(
+    state: &AppState,
+    req: &AdminApiRequest,
+) -> (u16, serde_json::Value) {
+    let segments: Vec<&str> = req
+        .path
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    let method = req.method.as_str();
+
+    if method == "GET" && segments == ["v1", "services"] {
+        return match get_service_status_inner(state) {
+            Ok(services) => (200, serde_json::json!({ "services": services })),
+            Err(err) => (500, serde_json::json!({ "error": err.to_string() })),
+        };
+    }
+
+    if method == "POST" && segments.len() == 4 && segments[0] == "v1" && segments[1] == "services" {
+        let service_id = segments[2];
+        let action = segments[3];
+        if !matches!(action, "start" | "stop" | "restart") {
+            return (404, serde_json::json!({ "error": "not found" }));
+        }
+        return match perform_service_action_inner(state, service_id, action, "http").await {
+            Ok(services) => (200, serde_json::json!({ "services": services })),
+            Err(err) => (500, serde_json::json!({ "error": err })),
+        };
+    }
+
+    if method == "GET" && segments == ["v1", "availability"] {
+        let force_refresh = req.query.get("force_refresh").map(|v| v == "true").unwrap_or(false);
+        return match get_cached_availability_report(state, force_refresh) {
+            Ok(report) => (
+                200,
+                serde_json::to_value(report).unwrap_or(serde_json::Value::Null),
+            ),
+            Err(err) => (500, serde_json::json!({ "error": err })),
+        };
+    }
+
+    if method == "GET" && segments == ["v1", "gateway", "status"] {
+        return match gateway_status_inner(state) {
+            Ok(status) => (
+                200,
+                serde_json::to_value(status).unwrap_or(serde_json::Value::Null),
+            ),
+            Err(err) => (500, serde_json::json!({ "error": err })),
+        };
+    }
+
+    if method == "POST" && segments == ["v1", "gateway", "start"] {
+        return match gateway_start_inner(state) {
+            Ok(status) => (
+                200,
+                serde_json::to_value(status).unwrap_or(serde_json::Value::Null),
+            ),
+            Err(err) => (500, serde_json::json!({ "error": err })),
+        };
+    }
+
+    if method == "POST" && segments == ["v1", "gateway", "stop"] {
+        return match gateway_stop_inner(state) {
+            Ok(status) => (
+                200,
+                serde_json::to_value(status).unwrap_or(serde_json::Value::Null),
+            ),
+            Err(err) => (500, serde_json::json!({ "error": err })),
+        };
+    }
+
+    if method == "POST" && segments == ["v1", "gateway", "login"] {
+        let parsed: serde_json::Value = if req.body.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::from_slice(&req.body).unwrap_or(serde_json::Value::Null)
+        };
+        let provider = parsed
+            .get("provider")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let interactive_login = parsed.get("interactive_login").and_then(|v| v.as_bool());
+        let timeout_seconds = parsed
+            .get("timeout_seconds")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32);
+        return match gateway_login_inner(state, provider, interactive_login, timeout_seconds) {
+            Ok(status) => (
+                200,
+                serde_json::to_value(status).unwrap_or(serde_json::Value::Null),
+            ),
+            Err(err) => (500, serde_json::json!({ "error": err })),
+        };
+    }
+
+    (404, serde_json::json!({ "error": "not found" }))
+}
+
+async fn handle_admin_api_connection(app: tauri::AppHandle, mut stream: tokio::net::TcpStream) {
+    use tokio::io::AsyncWriteExt;
+
+    let req = match read_admin_api_request(&mut stream).await {
+        Ok(req) => req,
+        Err(_) => return,
+    };
+
+    let state = app.state::<AppState>();
+    let (token, _) = admin_api_config(&state);
+    let authorized = matches!((&token, &req.bearer_token), (Some(expected), Some(provided)) if expected == provided);
+
+    if !authorized {
+        let response = admin_api_json_response(401, &serde_json::json!({ "error": "unauthorized" }));
+        let _ = stream.write_all(&response).await;
+        return;
+    }
+
+    if req.method == "GET" && req.path.trim_matches('/') == "metrics" {
+        let response =
+            admin_api_text_response(200, "text/plain; version=0.0.4", &render_prometheus_metrics(&state).await);
+        let _ = stream.write_all(&response).await;
+        return;
+    }
+
+    let (status, body) = route_admin_api_request(&state, &req).await;
+    let response = admin_api_json_response(status, &body);
+    let _ = stream.write_all(&response).await;
+}
+
+/// Spawned from `setup()`: binds to loopback and serves the same
+/// service/gateway/availability surface as the Tauri commands over plain
+/// HTTP, guarded by the bearer token in `OPENCLAW_ADMIN_API_TOKEN`. Exists so
+/// operators can drive the app (or wire it into external tooling) without
+/// going through the desktop window.
+async fn run_admin_http_api(app: tauri::AppHandle) {
+    let port = {
+        let state = app.state::<AppState>();
+        admin_api_config(&state).1
+    };
+
+    let Ok(listener) = tokio::net::TcpListener::bind(("127.0.0.1", port)).await else {
+        return;
+    };
+
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let app = app.clone();
+        tokio::spawn(async move {
+            handle_admin_api_connection(app, stream).await;
+        });
+    }
+}
+
+// ============================================================================
+// LSP Server Mode
+// ============================================================================
+
+/// `.env.v4.local` key that switches the binary into LSP server mode
+/// instead of launching the desktop app. Stdio is exclusive to the
+/// language-server protocol once this is set, so the two modes can't share
+/// a process.
+const LSP_MODE_ENV_KEY: &str = "OPENCLAW_LSP_MODE";
+
+/// Subset of `initialize`'s free-form `capabilities` object we actually
+/// branch on. Everything else the client advertises is ignored -- we don't
+/// implement enough of the spec to need it.
+#[derive(Debug, Clone, Copy, Default)]
+struct LspClientCapabilities {
+    snippet_support: bool,
+    insert_replace_support: bool,
+    additional_text_edits_support: bool,
+}
+
+impl LspClientCapabilities {
+    fn from_initialize_params(params: &serde_json::Value) -> Self {
+        let completion_item =
+            params.pointer("/capabilities/textDocument/completion/completionItem");
+        let snippet_support = completion_item
+            .and_then(|c| c.get("snippetSupport"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let insert_replace_support = completion_item
+            .and_then(|c| c.get("insertReplaceSupport"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let additional_text_edits_support = completion_item
+            .and_then(|c| c.get("resolveSupport"))
+            .and_then(|r| r.get("properties"))
+            .and_then(|p| p.as_array())
+            .map(|props| props.iter().any(|p| p.as_str() == Some("additionalTextEdits")))
+            .unwrap_or(false);
+        LspClientCapabilities {
+            snippet_support,
+            insert_replace_support,
+            additional_text_edits_support,
+        }
+    }
+}
+
+/// Fallback chain the LSP subsystem forwards completions to: the raw chain
+/// `openclaw` currently reports, reordered by the same declarative policy
+/// (`load_fallback_policy_config`/`compute_fallbacks_with_policy`) the
+/// desktop app applies, so both surfaces prioritize models identically.
+fn lsp_fallback_chain(state: &AppState) -> Vec<String> {
+    let current = run_openclaw_json(&["models", "fallbacks", "list", "--json"])
+        .ok()
+        .and_then(|json| json.get("fallbacks").and_then(|v| v.as_array()).cloned())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+    let policy = load_fallback_policy_config(state);
+    compute_fallbacks_with_policy(current, &policy)
+}
+
+/// `status_of` callback for `select_active_model_cached`: queries live
+/// `GatewayStatus` for `model` via the same dispatcher `gateway_status_inner`
+/// uses. A dispatcher failure or unknown model comes back as an all-`false`
+/// status, which the selector treats like any other unhealthy entry and
+/// just skips past.
+fn lsp_model_status(state: &AppState, model: &str) -> GatewayStatus {
+    run_dispatcher_json(state, &["gateway-status", "--model", model])
+        .map(|v| parse_gateway_status(&v))
+        .unwrap_or_else(|err| GatewayStatus {
+            last_error: err.to_string(),
+            ..GatewayStatus::default()
+        })
+}
+
+/// Sends `prompt` to `model` via the CLI and returns the completion text.
+fn lsp_complete_with_model(model: &str, prompt: &str) -> Result<String, String> {
+    let out = run_openclaw_json(&["complete", "--model", model, "--prompt", prompt, "--json"])?;
+    out.get("text")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("no completion text in response from {}", model))
+}
+
+/// Forwards `prompt` to whichever model `select_active_model_cached`
+/// currently resolves to. On a provider error mid-request, drops that
+/// model from the candidate list and tries the next healthy one instead
+/// of failing the LSP request outright. Returns the text plus the model
+/// that actually served it, so callers can log which provider answered.
+fn lsp_complete_via_fallback_chain(
+    state: &AppState,
+    fallbacks: &[String],
+    prompt: &str,
+) -> Result<(String, String), String> {
+    let mut remaining: Vec<String> = fallbacks.to_vec();
+    let mut last_err = "no healthy model in fallback chain".to_string();
+    while !remaining.is_empty() {
+        let Some(model) =
+            select_active_model_cached(state, &remaining, |m| lsp_model_status(state, m))
+        else {
+            break;
+        };
+        match lsp_complete_with_model(&model, prompt) {
+            Ok(text) => {
+                eprintln!("[lsp] served completion with active model: {}", model);
+                return Ok((text, model));
+            }
+            Err(err) => {
+                eprintln!(
+                    "[lsp] model {} failed mid-request, falling through to next candidate: {}",
+                    model, err
+                );
+                last_err = err;
+                remaining.retain(|m| m != &model);
+            }
+        }
+    }
+    Err(last_err)
+}
+
+/// Converts an LSP `Position` (0-based line, UTF-16-ish character offset --
+/// we treat it as a char count, which is fine for the ASCII/source-code
+/// prompts this subsystem deals with) into a byte offset into `text`.
+fn lsp_offset_for_position(text: &str, position: &serde_json::Value) -> usize {
+    let line = position.get("line").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+    let character = position.get("character").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+    let mut offset = 0;
+    for (i, l) in text.split('\n').enumerate() {
+        if i == line {
+            let chars: Vec<char> = l.chars().collect();
+            let take = character.min(chars.len());
+            offset += chars[..take].iter().map(|c| c.len_utf8()).sum::<usize>();
+            return offset;
+        }
+        offset += l.len() + 1;
+    }
+    text.len()
+}
+
+/// Builds the prompt for `textDocument/completion`: everything in the open
+/// document up to the cursor. An unknown `uri` (no `didOpen` seen yet)
+/// yields an empty prompt rather than failing the request.
+fn lsp_completion_prompt(documents: &HashMap<String, String>, params: &serde_json::Value) -> String {
+    let uri = params
+        .pointer("/textDocument/uri")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let position = params
+        .get("position")
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!({ "line": 0, "character": 0 }));
+    match documents.get(uri) {
+        Some(text) => {
+            let offset = lsp_offset_for_position(text, &position).min(text.len());
+            text[..offset].to_string()
+        }
+        None => String::new(),
+    }
+}
+
+/// Turns one resolved completion string into a single `CompletionItem`
+/// whose edit is a proper `TextEdit`/`InsertReplaceEdit` (rather than a
+/// bare string), matching whatever the client advertised in `initialize`,
+/// so the host editor can apply the patch reliably.
+fn lsp_completion_item(
+    capabilities: LspClientCapabilities,
+    position: &serde_json::Value,
+    text: &str,
+) -> serde_json::Value {
+    let point = position.clone();
+    let range = serde_json::json!({ "start": point, "end": point });
+    let text_edit = if capabilities.insert_replace_support {
+        serde_json::json!({ "insert": range, "replace": range, "newText": text })
+    } else {
+        serde_json::json!({ "range": range, "newText": text })
+    };
+    let label: String = text.lines().next().unwrap_or(text).chars().take(60).collect();
+    let mut item = serde_json::json!({
+        "label": label,
+        // 1 = PlainText, 2 = Snippet (LSP `InsertTextFormat`).
+        "insertTextFormat": if capabilities.snippet_support { 2 } else { 1 },
+        "textEdit": text_edit,
+    });
+    if capabilities.additional_text_edits_support {
+        item["additionalTextEdits"] = serde_json::json!([]);
+    }
+    item
+}
+
+/// Builds the `codeAction` response applying `text` as a `WorkspaceEdit`
+/// over `range` in `uri`, rather than a command the client has to
+/// round-trip through `workspace/executeCommand`.
+fn lsp_code_action_response(uri: &str, range: &serde_json::Value, text: &str) -> serde_json::Value {
+    serde_json::json!([{
+        "title": "Apply model suggestion",
+        "kind": "quickfix",
+        "edit": {
+            "changes": { uri: [{ "range": range, "newText": text }] },
+        },
+    }])
+}
+
+/// Minimal Content-Length-framed JSON-RPC reader for the LSP wire format
+/// (headers terminated by a blank line, `\r\n` line endings) -- the same
+/// header/body split `read_admin_api_request` does for HTTP, adapted to
+/// stdio. `Ok(None)` means the client closed its end.
+fn read_lsp_message(reader: &mut impl std::io::BufRead) -> Result<Option<serde_json::Value>, String> {
+    let mut content_length: usize = 0;
+    loop {
+        let mut line = String::new();
+        let n = reader.read_line(&mut line).map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if content_length == 0 {
+        return Ok(Some(serde_json::Value::Null));
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+    serde_json::from_slice(&body).map(Some).map_err(|e| e.to_string())
+}
+
+fn write_lsp_message(writer: &mut impl std::io::Write, value: &serde_json::Value) {
+    use std::io::Write as _;
+    let body = serde_json::to_vec(value).unwrap_or_default();
+    let _ = write!(writer, "Content-Length: {}\r\n\r\n", body.len());
+    let _ = writer.write_all(&body);
+    let _ = writer.flush();
+}
+
+/// Runs the language-server subsystem over stdio: reads Content-Length
+/// framed JSON-RPC requests, serves `initialize`, `textDocument/didOpen`
+/// `/didChange`/`didClose` (to keep a prompt source of truth),
+/// `textDocument/completion`, and `textDocument/codeAction` against
+/// whichever model the fallback chain currently resolves to, and returns
+/// once the client sends `exit`.
+fn run_lsp_server_stdio(state: &AppState) {
+    use std::io::BufRead as _;
+
+    let stdin = std::io::stdin();
+    let mut reader = stdin.lock();
+    let stdout = std::io::stdout();
+    let mut writer = stdout.lock();
+    let mut capabilities = LspClientCapabilities::default();
+    let mut documents: HashMap<String, String> = HashMap::new();
+
+    eprintln!("[lsp] server starting over stdio");
+
+    loop {
+        let message = match read_lsp_message(&mut reader) {
+            Ok(Some(m)) => m,
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("[lsp] malformed message, shutting down: {}", err);
+                break;
+            }
+        };
+        let id = message.get("id").cloned();
+        let method = message.get("method").and_then(|v| v.as_str()).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(serde_json::Value::Null);
+
+        match method {
+            "initialize" => {
+                capabilities = LspClientCapabilities::from_initialize_params(&params);
+                let result = serde_json::json!({
+                    "capabilities": {
+                        "textDocumentSync": 1,
+                        "completionProvider": { "resolveProvider": false },
+                        "codeActionProvider": true,
+                    }
+                });
+                write_lsp_message(
+                    &mut writer,
+                    &serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                );
+            }
+            "initialized" => {}
+            "textDocument/didOpen" => {
+                if let (Some(uri), Some(text)) = (
+                    params.pointer("/textDocument/uri").and_then(|v| v.as_str()),
+                    params.pointer("/textDocument/text").and_then(|v| v.as_str()),
+                ) {
+                    documents.insert(uri.to_string(), text.to_string());
+                }
+            }
+            "textDocument/didChange" => {
+                if let Some(uri) = params.pointer("/textDocument/uri").and_then(|v| v.as_str()) {
+                    if let Some(text) = params
+                        .pointer("/contentChanges")
+                        .and_then(|v| v.as_array())
+                        .and_then(|changes| changes.last())
+                        .and_then(|change| change.get("text"))
+                        .and_then(|v| v.as_str())
+                    {
+                        documents.insert(uri.to_string(), text.to_string());
+                    }
+                }
+            }
+            "textDocument/didClose" => {
+                if let Some(uri) = params.pointer("/textDocument/uri").and_then(|v| v.as_str()) {
+                    documents.remove(uri);
+                }
+            }
+            "shutdown" => {
+                write_lsp_message(
+                    &mut writer,
+                    &serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": serde_json::Value::Null }),
+                );
+            }
+            "exit" => break,
+            "textDocument/completion" => {
+                let position = params
+                    .get("position")
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!({ "line": 0, "character": 0 }));
+                let prompt = lsp_completion_prompt(&documents, &params);
+                let fallbacks = lsp_fallback_chain(state);
+                let result = match lsp_complete_via_fallback_chain(state, &fallbacks, &prompt) {
+                    Ok((text, _model)) => serde_json::json!({
+                        "isIncomplete": false,
+                        "items": [lsp_completion_item(capabilities, &position, &text)],
+                    }),
+                    Err(err) => {
+                        eprintln!("[lsp] completion request failed, no healthy model answered: {}", err);
+                        serde_json::json!({ "isIncomplete": false, "items": [] })
+                    }
+                };
+                if id.is_some() {
+                    write_lsp_message(
+                        &mut writer,
+                        &serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    );
+                }
+            }
+            "textDocument/codeAction" => {
+                let uri = params
+                    .pointer("/textDocument/uri")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let range = params.get("range").cloned().unwrap_or_else(|| {
+                    serde_json::json!({ "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 0 } })
+                });
+                let prompt = format!(
+                    "Suggest a code action for the selected range in {}:\n{}",
+                    uri,
+                    documents.get(&uri).cloned().unwrap_or_default()
+                );
+                let fallbacks = lsp_fallback_chain(state);
+                let result = match lsp_complete_via_fallback_chain(state, &fallbacks, &prompt) {
+                    Ok((text, _model)) => lsp_code_action_response(&uri, &range, &text),
+                    Err(err) => {
+                        eprintln!("[lsp] code action request failed, no healthy model answered: {}", err);
+                        serde_json::json!([])
+                    }
+                };
+                if id.is_some() {
+                    write_lsp_message(
+                        &mut writer,
+                        &serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    );
+                }
+            }
+            "" => {}
+            other => {
+                if id.is_some() {
+                    write_lsp_message(
+                        &mut writer,
+                        &serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": { "code": -32601, "message": format!("method not found: {}", other) },
+                        }),
+                    );
+                }
+            }
+        }
+    }
+
+    eprintln!("[lsp] server shutting down");
 }
 
-#[tauri::command]
-fn delete_api_key(provider: String) -> Result<(), String> {
-    let mut profiles = read_auth_profiles()?;
+pub fn run() {
+    let state = AppState::default();
 
-    let profile_key = format!("{}:default", provider);
-    if let Some(profiles_obj) = profiles.get_mut("profiles").and_then(|p| p.as_object_mut()) {
-        profiles_obj.remove(&profile_key);
+    let lsp_mode = read_env_map(&PathBuf::from(&state.config_path).join(".env.v4.local"))
+        .get(LSP_MODE_ENV_KEY)
+        .map(|v| v.trim() == "1")
+        .unwrap_or(false);
+    if lsp_mode {
+        run_lsp_server_stdio(&state);
+        return;
     }
 
-    write_auth_profiles(&profiles)
-}
-
-// ============================================================================
-// Entry Point
-// ============================================================================
+    if let Err(err) = run_schema_migrations(&state.db_pool) {
+        eprintln!("[startup] {}", err);
+        std::process::exit(1);
+    }
+    if let Err(err) = reconcile_orphaned_jobs_inner(&state) {
+        eprintln!("[startup] failed to reconcile orphaned jobs: {}", err);
+    }
+    install_panic_hook(state.logs_dir.clone(), state.config_path.clone());
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .manage(AppState::default())
+        .manage(state)
         .invoke_handler(tauri::generate_handler![
             get_service_status,
             start_all_services,
@@ -5536,6 +12421,7 @@ pub fn run() {
             restart_service,
             run_preflight_check,
             auto_fix_preflight,
+            resolve_next_vision_backend,
             start_openclaw,
             start_openclaw_v2,
             start_telegram_bot_v2,
@@ -5554,6 +12440,17 @@ pub fn run() {
             save_env_settings,
             get_jobs,
             get_job_milestones,
+            requeue_job,
+            requeue_failed_jobs,
+            get_retryable_jobs,
+            get_job_counts,
+            cancel_job,
+            set_job_priority,
+            purge_jobs,
+            get_schema_status,
+            reconcile_jobs,
+            get_prometheus_metrics,
+            get_metrics,
             get_overview_metrics,
             get_overview_trends,
             get_queue_snapshot,
@@ -5567,27 +12464,65 @@ pub fn run() {
             export_run_summary,
             list_verify_artifacts,
             get_quality_report,
+            poll_quality_report,
             get_verify_folder_path,
             get_kb_sync_report,
+            poll_kb_sync,
             get_kb_stats,
             kb_sync_now,
             list_kb_files,
+            export_kb_files,
             list_glossary_terms,
+            export_glossary_terms,
             upsert_glossary_term,
             delete_glossary_term,
+            upsert_glossary_batch,
+            delete_glossary_batch,
             lookup_glossary_text,
             get_docker_status,
             start_docker_services,
             stop_docker_services,
+            compose_up,
+            compose_down,
+            stream_container_logs,
+            stop_container_log_stream,
             open_in_finder,
             read_log_file,
             get_api_providers,
             get_api_usage,
             set_api_key,
             delete_api_key,
+            refresh_oauth_token,
+            create_api_key_profile,
+            list_api_key_profiles,
+            delete_api_key_profile,
             get_model_availability_report,
+            list_crash_reports,
+            retry_crash_upload,
+            spawn_dispatcher,
+            poll_completed,
         ])
         .setup(|app| {
+            // Recomputes model availability on a schedule, keeping fresh the
+            // cache that `get_cached_availability_report` serves.
+            let availability_refresher_handle = app.handle().clone();
+            tokio::spawn(run_availability_cache_refresher(availability_refresher_handle));
+
+            // Acts on cooldown/expired-OAuth states instead of just reporting
+            // them: reschedules around cooldowns, backs off transient
+            // failures, and auto-retries gateway_login for expired OAuth.
+            let recovery_scheduler_handle = app.handle().clone();
+            tokio::spawn(run_recovery_scheduler(recovery_scheduler_handle));
+
+            // Push service/availability updates instead of making the UI poll.
+            let watcher_handle = app.handle().clone();
+            tokio::spawn(run_status_watcher(watcher_handle));
+
+            // Serve the same service/gateway/availability surface over a
+            // token-guarded loopback HTTP API for external tooling.
+            let admin_api_handle = app.handle().clone();
+            tokio::spawn(run_admin_http_api(admin_api_handle));
+
             // Create system tray
             let open_item = MenuItem::with_id(app, "open", "Open Dashboard", true, None::<&str>)
                 .expect("Failed to create open menu item");
@@ -5680,7 +12615,7 @@ mod tests {
 
         let a = compute_agent_availability("translator-core", &status, &availability);
         assert!(!a.runnable_now);
-        assert_eq!(a.route[0].state, "cooldown");
+        assert_eq!(a.route[0].state, RouteModelState::Cooldown);
         assert!(a
             .blocked_reasons
             .iter()
@@ -5707,7 +12642,7 @@ mod tests {
 
         let a = compute_agent_availability("translator-core", &status, &availability);
         assert!(!a.runnable_now);
-        assert_eq!(a.route[0].state, "expired");
+        assert_eq!(a.route[0].state, RouteModelState::Expired);
         assert!(a.blocked_reasons.iter().any(|r| r.contains("OAuth")));
     }
 
@@ -5732,7 +12667,7 @@ mod tests {
         let a = compute_agent_availability("glm-reviewer", &status, &availability);
         assert!(a.runnable_now);
         assert_eq!(a.first_runnable_model, Some("zai/glm-5".to_string()));
-        assert_eq!(a.route[0].state, "ok");
+        assert_eq!(a.route[0].state, RouteModelState::Ok);
     }
 
     #[test]
@@ -5836,6 +12771,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compute_fallbacks_with_policy_supports_new_provider_families() {
+        let policy = FallbackPolicyConfig {
+            preferred: vec!["acme/flagship-1".to_string()],
+            append_prefixes: vec!["acme/legacy-".to_string()],
+        };
+        let current = vec![
+            "acme/legacy-2".to_string(),
+            "openai-codex/gpt-5.2".to_string(),
+            "acme/flagship-1".to_string(),
+        ];
+        let desired = compute_fallbacks_with_policy(current, &policy);
+        assert_eq!(
+            desired,
+            vec![
+                "acme/flagship-1".to_string(),
+                "openai-codex/gpt-5.2".to_string(),
+                "acme/legacy-2".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn compute_fallbacks_with_policy_moves_append_prefixes_last_with_no_preferred_models() {
+        let policy = FallbackPolicyConfig {
+            preferred: vec![],
+            append_prefixes: vec!["acme/legacy-".to_string()],
+        };
+        let current = vec![
+            "acme/legacy-2".to_string(),
+            "openai-codex/gpt-5.2".to_string(),
+        ];
+        let desired = compute_fallbacks_with_policy(current, &policy);
+        assert_eq!(
+            desired,
+            vec!["openai-codex/gpt-5.2".to_string(), "acme/legacy-2".to_string()]
+        );
+    }
+
     #[test]
     fn parse_gateway_status_reads_nested_result_payload() {
         let payload = json!({
@@ -5873,4 +12847,574 @@ mod tests {
         assert!(!status.running);
         assert_eq!(status.last_error, "gateway_unavailable");
     }
+
+    fn test_gateway_status(healthy: bool, last_error: &str) -> GatewayStatus {
+        GatewayStatus {
+            running: healthy,
+            healthy,
+            logged_in: healthy,
+            last_error: last_error.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn select_active_model_skips_unhealthy_entries() {
+        let fallbacks = vec![
+            "openai-codex/gpt-5.2".to_string(),
+            "moonshot/kimi-k2.5".to_string(),
+            "zai/glm-5".to_string(),
+        ];
+        let selected = select_active_model(&fallbacks, |model| match model {
+            "openai-codex/gpt-5.2" => test_gateway_status(false, "rate_limited"),
+            "moonshot/kimi-k2.5" => test_gateway_status(true, ""),
+            _ => test_gateway_status(true, ""),
+        });
+        assert_eq!(selected, Some("moonshot/kimi-k2.5".to_string()));
+    }
+
+    #[test]
+    fn select_active_model_returns_none_when_all_unhealthy() {
+        let fallbacks = vec!["openai-codex/gpt-5.2".to_string()];
+        let selected = select_active_model(&fallbacks, |_| test_gateway_status(false, "down"));
+        assert_eq!(selected, None);
+    }
+
+    #[test]
+    fn select_active_model_cached_reuses_recent_probe() {
+        let state = AppState::default();
+        let fallbacks = vec!["moonshot/kimi-k2.5".to_string()];
+        let calls = std::sync::atomic::AtomicU64::new(0);
+
+        let probe = |_: &str| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            test_gateway_status(true, "")
+        };
+
+        let first = select_active_model_cached(&state, &fallbacks, probe);
+        let second = select_active_model_cached(&state, &fallbacks, probe);
+        assert_eq!(first, Some("moonshot/kimi-k2.5".to_string()));
+        assert_eq!(second, first);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn select_active_model_cached_logs_transition_on_change() {
+        let state = AppState::default();
+        let fallbacks = vec![
+            "openai-codex/gpt-5.2".to_string(),
+            "moonshot/kimi-k2.5".to_string(),
+        ];
+
+        // Simulate a recent probe that already found the preferred model
+        // unhealthy, so the wrapper's cache short-circuits straight to the
+        // fallback without calling `status_of` again.
+        *state.active_model_current.lock().unwrap() = Some("openai-codex/gpt-5.2".to_string());
+        state.active_model_status_cache.lock().unwrap().insert(
+            "openai-codex/gpt-5.2".to_string(),
+            ActiveModelStatusEntry {
+                status: test_gateway_status(false, "rate_limited"),
+                cached_at_ms: now_epoch_ms(),
+            },
+        );
+
+        let selected = select_active_model_cached(&state, &fallbacks, |_| {
+            test_gateway_status(true, "")
+        });
+
+        assert_eq!(selected, Some("moonshot/kimi-k2.5".to_string()));
+        assert_eq!(
+            *state.active_model_current.lock().unwrap(),
+            Some("moonshot/kimi-k2.5".to_string())
+        );
+    }
+
+    #[test]
+    fn circuit_trips_open_after_failure_ratio_exceeded() {
+        let state = AppState::default();
+        let now = now_epoch_ms();
+
+        assert!(circuit_allows_probe(&state, "chatgpt-web", now));
+        circuit_record_outcome(&state, "chatgpt-web", false, now);
+        circuit_record_outcome(&state, "chatgpt-web", false, now);
+        circuit_record_outcome(&state, "chatgpt-web", false, now);
+
+        assert!(!circuit_allows_probe(&state, "chatgpt-web", now));
+        assert_eq!(
+            state.circuit_breakers.lock().unwrap().get("chatgpt-web").unwrap().state,
+            CircuitState::Open
+        );
+    }
+
+    #[test]
+    fn circuit_does_not_trip_below_min_samples() {
+        let state = AppState::default();
+        let now = now_epoch_ms();
+
+        circuit_record_outcome(&state, "chatgpt-web", false, now);
+        circuit_record_outcome(&state, "chatgpt-web", false, now);
+
+        assert!(circuit_allows_probe(&state, "chatgpt-web", now));
+    }
+
+    #[test]
+    fn circuit_moves_to_half_open_after_backoff_and_closes_on_success() {
+        let state = AppState::default();
+        let now = now_epoch_ms();
+
+        for _ in 0..CIRCUIT_MIN_SAMPLES {
+            circuit_record_outcome(&state, "chatgpt-web", false, now);
+        }
+        assert!(!circuit_allows_probe(&state, "chatgpt-web", now));
+
+        // Before the backoff elapses the breaker stays open.
+        assert!(!circuit_allows_probe(&state, "chatgpt-web", now + 1_000));
+
+        // After the backoff elapses, one trial probe is allowed (HalfOpen).
+        let past_backoff = now + next_backoff_delay_ms(1) + 1;
+        assert!(circuit_allows_probe(&state, "chatgpt-web", past_backoff));
+        assert_eq!(
+            state.circuit_breakers.lock().unwrap().get("chatgpt-web").unwrap().state,
+            CircuitState::HalfOpen
+        );
+
+        circuit_record_outcome(&state, "chatgpt-web", true, past_backoff);
+        assert_eq!(
+            state.circuit_breakers.lock().unwrap().get("chatgpt-web").unwrap().state,
+            CircuitState::Closed
+        );
+        assert!(circuit_allows_probe(&state, "chatgpt-web", past_backoff));
+    }
+
+    #[test]
+    fn circuit_reopens_with_longer_backoff_on_half_open_failure() {
+        let state = AppState::default();
+        let now = now_epoch_ms();
+
+        for _ in 0..CIRCUIT_MIN_SAMPLES {
+            circuit_record_outcome(&state, "chatgpt-web", false, now);
+        }
+        let first_backoff = next_backoff_delay_ms(1);
+        assert!(circuit_allows_probe(&state, "chatgpt-web", now + first_backoff + 1));
+
+        circuit_record_outcome(&state, "chatgpt-web", false, now + first_backoff + 1);
+        assert_eq!(
+            state.circuit_breakers.lock().unwrap().get("chatgpt-web").unwrap().consecutive_failures,
+            2
+        );
+
+        // The breaker re-opened, so the shorter first backoff isn't enough
+        // to allow another probe yet.
+        assert!(!circuit_allows_probe(
+            &state,
+            "chatgpt-web",
+            now + first_backoff + 1 + first_backoff
+        ));
+    }
+
+    #[test]
+    fn select_active_model_cached_skips_model_with_open_circuit() {
+        let state = AppState::default();
+        let now = now_epoch_ms();
+        for _ in 0..CIRCUIT_MIN_SAMPLES {
+            circuit_record_outcome(&state, "openai-codex/gpt-5.2", false, now);
+        }
+
+        let fallbacks = vec![
+            "openai-codex/gpt-5.2".to_string(),
+            "moonshot/kimi-k2.5".to_string(),
+        ];
+        let selected = select_active_model_cached(&state, &fallbacks, |_| {
+            test_gateway_status(true, "")
+        });
+
+        assert_eq!(selected, Some("moonshot/kimi-k2.5".to_string()));
+    }
+
+    #[test]
+    fn select_active_model_cached_defers_half_open_transition_to_a_fresh_cache_entry() {
+        // Regression test for a bug where the TTL cache was consulted
+        // *after* the circuit breaker: a cache entry written at the moment
+        // the breaker tripped Open was still "fresh" once the (shorter)
+        // backoff elapsed, so the Open->HalfOpen transition fired but the
+        // stale cached status was returned anyway, without ever calling
+        // `status_of` or `circuit_record_outcome` -- leaving the breaker
+        // parked in HalfOpen with no recorded trial. With the cache
+        // checked first, a fresh entry must short-circuit before the
+        // breaker is ever consulted, so it stays Open (not dangling in
+        // HalfOpen) until the cache itself goes stale.
+        let state = AppState::default();
+        let now = now_epoch_ms();
+        let backoff = next_backoff_delay_ms(1);
+        let opened_at_ms = now - backoff - 1;
+
+        state.circuit_breakers.lock().unwrap().insert(
+            "moonshot/kimi-k2.5".to_string(),
+            ModelCircuitState {
+                state: CircuitState::Open,
+                outcomes: std::collections::VecDeque::new(),
+                consecutive_failures: 1,
+                opened_at_ms,
+            },
+        );
+        state.active_model_status_cache.lock().unwrap().insert(
+            "moonshot/kimi-k2.5".to_string(),
+            ActiveModelStatusEntry {
+                status: test_gateway_status(false, "down"),
+                cached_at_ms: opened_at_ms,
+            },
+        );
+
+        let calls = std::sync::atomic::AtomicU64::new(0);
+        let selected = select_active_model_cached(&state, &["moonshot/kimi-k2.5".to_string()], |_| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            test_gateway_status(true, "")
+        });
+
+        assert_eq!(selected, None);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert_eq!(
+            state.circuit_breakers.lock().unwrap().get("moonshot/kimi-k2.5").unwrap().state,
+            CircuitState::Open
+        );
+    }
+
+    #[test]
+    fn select_active_model_cached_gives_half_open_model_a_live_probe_once_cache_expires() {
+        let state = AppState::default();
+        let now = now_epoch_ms();
+        let backoff = next_backoff_delay_ms(1);
+        let opened_at_ms = now - backoff - ACTIVE_MODEL_STATUS_TTL_MS - 1;
+
+        state.circuit_breakers.lock().unwrap().insert(
+            "moonshot/kimi-k2.5".to_string(),
+            ModelCircuitState {
+                state: CircuitState::Open,
+                outcomes: std::collections::VecDeque::new(),
+                consecutive_failures: 1,
+                opened_at_ms,
+            },
+        );
+        state.active_model_status_cache.lock().unwrap().insert(
+            "moonshot/kimi-k2.5".to_string(),
+            ActiveModelStatusEntry {
+                status: test_gateway_status(false, "down"),
+                cached_at_ms: opened_at_ms,
+            },
+        );
+
+        let fallbacks = vec!["moonshot/kimi-k2.5".to_string()];
+        let selected = select_active_model_cached(&state, &fallbacks, |_| {
+            test_gateway_status(true, "")
+        });
+
+        assert_eq!(selected, Some("moonshot/kimi-k2.5".to_string()));
+        assert_eq!(
+            state.circuit_breakers.lock().unwrap().get("moonshot/kimi-k2.5").unwrap().state,
+            CircuitState::Closed
+        );
+    }
+
+    #[test]
+    fn compute_retry_delay_ms_doubles_per_attempt_up_to_the_cap() {
+        assert_eq!(compute_retry_delay_ms(0), RETRY_BASE_DELAY_MS);
+        assert_eq!(compute_retry_delay_ms(1), RETRY_BASE_DELAY_MS * 2);
+        assert_eq!(compute_retry_delay_ms(2), RETRY_BASE_DELAY_MS * 4);
+        assert_eq!(compute_retry_delay_ms(32), RETRY_MAX_DELAY_MS);
+    }
+
+    #[test]
+    fn format_human_bytes_picks_the_largest_unit_under_1024() {
+        assert_eq!(format_human_bytes(0), "0 B");
+        assert_eq!(format_human_bytes(1023), "1023 B");
+        assert_eq!(format_human_bytes(1024), "1.0 KiB");
+        assert_eq!(format_human_bytes(1536), "1.5 KiB");
+        assert_eq!(format_human_bytes(1024 * 1024), "1.0 MiB");
+        assert_eq!(format_human_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+
+    #[test]
+    fn format_human_bytes_caps_at_the_largest_unit() {
+        let huge = u64::MAX;
+        assert!(format_human_bytes(huge).ends_with(" TiB"));
+    }
+
+    #[test]
+    fn tabular_escape_field_passes_through_plain_values() {
+        assert_eq!(tabular_escape_field("plain value", ','), "plain value");
+    }
+
+    #[test]
+    fn tabular_escape_field_quotes_and_doubles_embedded_quotes() {
+        assert_eq!(
+            tabular_escape_field("has, a comma", ','),
+            "\"has, a comma\""
+        );
+        assert_eq!(
+            tabular_escape_field("has \"quotes\"", ','),
+            "\"has \"\"quotes\"\"\""
+        );
+        assert_eq!(tabular_escape_field("line\nbreak", ','), "\"line\nbreak\"");
+        assert_eq!(tabular_escape_field("has\ta tab", '\t'), "\"has\ta tab\"");
+    }
+
+    #[test]
+    fn find_active_profile_uses_last_good_pointer_when_present() {
+        let profiles = json!({
+            "lastGood": { "openrouter": "openrouter:b" },
+            "profiles": {
+                "openrouter:a": { "created_at": 200 },
+                "openrouter:b": { "created_at": 100 },
+            },
+        });
+        let (key, _) = find_active_profile(&profiles, "openrouter", 1_000).unwrap();
+        assert_eq!(key, "openrouter:b");
+    }
+
+    #[test]
+    fn find_active_profile_prefers_non_expired_over_newer_expired() {
+        let profiles = json!({
+            "profiles": {
+                "openrouter:newer_but_expired": { "created_at": 200, "expires_at": 50 },
+                "openrouter:older_but_live": { "created_at": 100, "expires_at": 5_000 },
+            },
+        });
+        let (key, _) = find_active_profile(&profiles, "openrouter", 1_000).unwrap();
+        assert_eq!(key, "openrouter:older_but_live");
+    }
+
+    #[test]
+    fn find_active_profile_breaks_ties_by_newest_created_at() {
+        let profiles = json!({
+            "profiles": {
+                "openrouter:older": { "created_at": 100, "expires_at": 5_000 },
+                "openrouter:newer": { "created_at": 200, "expires_at": 5_000 },
+            },
+        });
+        let (key, _) = find_active_profile(&profiles, "openrouter", 1_000).unwrap();
+        assert_eq!(key, "openrouter:newer");
+    }
+
+    #[test]
+    fn find_active_profile_falls_back_to_newest_expired_when_all_expired() {
+        let profiles = json!({
+            "profiles": {
+                "openrouter:older": { "created_at": 100, "expires_at": 50 },
+                "openrouter:newer": { "created_at": 200, "expires_at": 50 },
+            },
+        });
+        let (key, _) = find_active_profile(&profiles, "openrouter", 1_000).unwrap();
+        assert_eq!(key, "openrouter:newer");
+    }
+
+    #[test]
+    fn find_active_profile_returns_none_without_a_matching_prefix() {
+        let profiles = json!({
+            "profiles": {
+                "zai:only": { "created_at": 100 },
+            },
+        });
+        assert!(find_active_profile(&profiles, "openrouter", 1_000).is_none());
+    }
+
+    #[test]
+    fn encrypt_secret_round_trips_through_decrypt_secret() {
+        use base64::Engine;
+        // Deterministic fallback for sandboxes with no OS keychain backend;
+        // `load_master_key` only reaches this when the keychain entry is
+        // unavailable, so this doesn't fight a real provisioned key.
+        std::env::set_var(
+            "OPENCLAW_SECRET_KEY",
+            base64::engine::general_purpose::STANDARD.encode([7u8; 32]),
+        );
+
+        let ciphertext = encrypt_secret("super-secret-value").expect("encryption should succeed");
+        assert!(is_encrypted_secret(&ciphertext));
+        assert_ne!(ciphertext, "super-secret-value");
+
+        let plaintext = decrypt_secret(&ciphertext).expect("decryption should succeed");
+        assert_eq!(plaintext, "super-secret-value");
+    }
+
+    #[test]
+    fn decrypt_secret_passes_through_legacy_plaintext_values() {
+        assert_eq!(
+            decrypt_secret("plain-legacy-value").unwrap(),
+            "plain-legacy-value"
+        );
+    }
+
+    #[test]
+    fn ensure_job_retry_columns_adds_columns_once_and_is_idempotent() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE jobs (job_id TEXT PRIMARY KEY)", [])
+            .unwrap();
+
+        ensure_job_retry_columns(&conn).unwrap();
+        ensure_job_retry_columns(&conn).unwrap();
+
+        let mut stmt = conn.prepare("PRAGMA table_info(jobs)").unwrap();
+        let columns: Vec<String> = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        for expected in ["error_count", "last_try", "next_try"] {
+            assert_eq!(
+                columns.iter().filter(|c| c.as_str() == expected).count(),
+                1,
+                "expected exactly one {} column, got {:?}",
+                expected,
+                columns
+            );
+        }
+    }
+
+    #[test]
+    fn prometheus_escape_label_escapes_backslashes_quotes_and_newlines() {
+        assert_eq!(
+            prometheus_escape_label("back\\slash \"quote\" new\nline"),
+            "back\\\\slash \\\"quote\\\" new\\nline"
+        );
+    }
+
+    fn sample_overview_metrics() -> OverviewMetrics {
+        OverviewMetrics {
+            total_jobs: 10,
+            completed_jobs: 7,
+            failed_jobs: 1,
+            review_ready_jobs: 1,
+            running_jobs: 1,
+            backlog_jobs: 0,
+            success_rate: 70.0,
+            avg_turnaround_minutes: 12.5,
+            occupancy_rate: 42.0,
+            services_running: 2,
+            services_total: 3,
+            open_alerts: 1,
+            period_hours: 24,
+            generated_at: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn render_overview_prometheus_metrics_exposes_totals_queue_and_alerts() {
+        let metrics = sample_overview_metrics();
+        let queue = QueueSnapshot {
+            pending: 2,
+            running: 1,
+            review_ready: 1,
+            done: 7,
+            failed: 1,
+        };
+        let services = vec![ServiceStatus {
+            name: "translator-core".to_string(),
+            status: "running".to_string(),
+            pid: Some(123),
+            uptime: None,
+            restarts: 0,
+        }];
+        let alerts = vec![AlertItem {
+            id: "alert-1".to_string(),
+            title: "High failure rate".to_string(),
+            message: "".to_string(),
+            severity: AlertSeverity::Critical,
+            status: AlertStatus::Open,
+            source: "queue".to_string(),
+            metric_value: None,
+            created_at: 1_700_000_000_000,
+            action_label: None,
+        }];
+
+        let out = render_overview_prometheus_metrics(&metrics, &queue, &services, &alerts);
+
+        assert!(out.contains("translation_jobs_total 10\n"));
+        assert!(out.contains("translation_jobs_completed 7\n"));
+        assert!(out.contains("translation_queue_jobs{state=\"pending\"} 2\n"));
+        assert!(out.contains("translation_service_up{service=\"translator-core\"} 1\n"));
+        assert!(out.contains("translation_open_alerts{severity=\"critical\"} 1\n"));
+        assert!(out.contains("translation_open_alerts{severity=\"warning\"} 0\n"));
+    }
+
+    #[test]
+    fn render_provider_prometheus_metrics_exposes_auth_usage_and_openrouter_credits() {
+        let providers = vec![ApiProvider {
+            id: "openrouter".to_string(),
+            name: "OpenRouter".to_string(),
+            auth_type: ApiAuthType::Oauth,
+            status: ApiProviderStatus::Configured,
+            has_key: true,
+            email: None,
+            expires_at: Some(1_700_000_000_000),
+        }];
+        let mut activities = HashMap::new();
+        activities.insert(
+            "openrouter".to_string(),
+            ProviderActivity {
+                calls: 10,
+                errors: 2,
+                rate_limited: 0,
+                last_seen_at: None,
+                p50_latency_ms: None,
+                p95_latency_ms: None,
+                confidence: ApiUsageConfidence::High,
+            },
+        );
+        let usage = ApiUsage {
+            provider: "openrouter".to_string(),
+            used: 40,
+            limit: 100,
+            remaining: 60,
+            unit: "credits".to_string(),
+            reset_at: None,
+            fetched_at: 1_700_000_000_000,
+            source: ApiUsageSource::RealApi,
+            confidence: ApiUsageConfidence::High,
+            reason: None,
+            activity_calls_24h: None,
+            activity_errors_24h: None,
+            activity_success_rate: None,
+            activity_last_seen_at: None,
+            activity_rate_limited_24h: None,
+            activity_p50_latency_ms: None,
+            activity_p95_latency_ms: None,
+        };
+
+        let out = render_provider_prometheus_metrics(&providers, &activities, Some(&usage));
+
+        assert!(out.contains("provider_configured{provider=\"openrouter\",auth_type=\"oauth\"} 1\n"));
+        assert!(out.contains("provider_oauth_expires_seconds{provider=\"openrouter\"} 1700000000\n"));
+        assert!(out.contains("provider_calls_total{provider=\"openrouter\"} 10\n"));
+        assert!(out.contains("provider_errors_total{provider=\"openrouter\"} 2\n"));
+        assert!(out.contains("provider_success_rate{provider=\"openrouter\"} 0.8\n"));
+        assert!(out.contains("openrouter_credits_remaining 60\n"));
+        assert!(out.contains("openrouter_credits_used 40\n"));
+    }
+
+    #[test]
+    fn render_provider_prometheus_metrics_omits_openrouter_credits_when_unit_is_not_credits() {
+        let usage = ApiUsage {
+            provider: "openrouter".to_string(),
+            used: 40,
+            limit: 100,
+            remaining: 60,
+            unit: "tokens".to_string(),
+            reset_at: None,
+            fetched_at: 1_700_000_000_000,
+            source: ApiUsageSource::RealApi,
+            confidence: ApiUsageConfidence::High,
+            reason: None,
+            activity_calls_24h: None,
+            activity_errors_24h: None,
+            activity_success_rate: None,
+            activity_last_seen_at: None,
+            activity_rate_limited_24h: None,
+            activity_p50_latency_ms: None,
+            activity_p95_latency_ms: None,
+        };
+
+        let out = render_provider_prometheus_metrics(&[], &HashMap::new(), Some(&usage));
+
+        assert!(!out.contains("openrouter_credits_remaining"));
+    }
 }